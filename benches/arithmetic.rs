@@ -0,0 +1,65 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use dual_balanced_ternary::{ternary, DualBalancedTernary, DualBalancedTernaryDigit::*};
+
+/// a value with `n` integral digits and `n / 2` fractional digits, cycling through all digits
+fn operand(n: usize, offset: usize) -> DualBalancedTernary {
+  let digits = "123456789";
+  let pick = |i: usize| &digits[(i + offset) % 9..(i + offset) % 9 + 1];
+  let integral: String = (0..n).map(pick).collect();
+  let fractional: String = (n..n + n / 2).map(pick).collect();
+  ternary(&format!("&{}.{}", integral, fractional))
+}
+
+/// the former `+`, adding digits of `b` one by one with `add_at`, kept as a baseline
+fn add_by_add_at(a: &DualBalancedTernary, b: &DualBalancedTernary) -> DualBalancedTernary {
+  b.pairs().into_iter().fold(a.to_owned(), |acc, (idx, d)| acc.add_at(idx, d))
+}
+
+/// the former `*`, adding products of every two digits with `add_at`, kept as a baseline
+fn mul_by_add_at(a: &DualBalancedTernary, b: &DualBalancedTernary) -> DualBalancedTernary {
+  let mut result = ternary("&5");
+  for (a_idx, a_item) in a.pairs() {
+    for (b_idx, b_item) in b.pairs() {
+      let (carry, unit) = a_item * b_item;
+      result = result.add_at(a_idx + b_idx, unit);
+      if carry != Dbt5 {
+        result = result.add_at(a_idx + b_idx + 1, carry);
+      }
+    }
+  }
+  result
+}
+
+fn arithmetic(c: &mut Criterion) {
+  let a = operand(100, 0);
+  let b = operand(100, 4);
+  assert_eq!(add_by_add_at(&a, &b), &a + &b);
+  assert_eq!(mul_by_add_at(&a, &b), &a * &b);
+
+  c.bench_function("add 100 digits", |bench| {
+    bench.iter(|| black_box(a.to_owned()) + black_box(b.to_owned()))
+  });
+  c.bench_function("sub 100 digits", |bench| {
+    bench.iter(|| black_box(a.to_owned()) - black_box(b.to_owned()))
+  });
+  c.bench_function("mul 100 digits", |bench| {
+    bench.iter(|| black_box(a.to_owned()) * black_box(b.to_owned()))
+  });
+  c.bench_function("add_at 100 digits", |bench| bench.iter(|| black_box(&a).add_at(black_box(0), Dbt8)));
+  c.bench_function("add 100 digits by add_at", |bench| {
+    bench.iter(|| add_by_add_at(black_box(&a), black_box(&b)))
+  });
+  c.bench_function("mul 100 digits by add_at", |bench| {
+    bench.iter(|| mul_by_add_at(black_box(&a), black_box(&b)))
+  });
+
+  let long_a = operand(2000, 0);
+  let long_b = operand(2000, 4);
+  c.bench_function("mul 2000 digits", |bench| {
+    bench.iter(|| black_box(long_a.to_owned()) * black_box(long_b.to_owned()))
+  });
+}
+
+criterion_group!(benches, arithmetic);
+criterion_main!(benches);
@@ -1,7 +1,7 @@
-use dual_balanced_ternary::{ternary, DualBalancedTernary};
+use dual_balanced_ternary::{ternary, DbtError, DualBalancedTernary};
 use std::convert::{TryFrom, TryInto};
 
-pub fn main() -> Result<(), String> {
+pub fn main() -> Result<(), DbtError> {
   println!("{:?}", TryInto::<Vec<u8>>::try_into(ternary("&1.1"))?);
   println!("{:?}", TryInto::<Vec<u8>>::try_into(ternary("&14.14"))?);
 
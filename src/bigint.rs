@@ -0,0 +1,106 @@
+//! `num-bigint` conversions, behind `num-bigint` feature.
+//!
+//! Like conversions of primitive integers, a `BigInt` is placed on `1` direction,
+//! and pairs are `(x, y)` for `3` and `1` directions. Values of any length convert exactly.
+
+use std::convert::TryFrom;
+
+use num_bigint::{BigInt, BigUint, Sign};
+
+use crate::complex::ComplexXy;
+use crate::error::DbtError;
+use crate::primes::DualBalancedTernary;
+
+/// balanced ternary trits of `n`, lowest first
+fn balanced_trits(n: &BigInt) -> Vec<i64> {
+  let sign = if n.sign() == Sign::Minus { -1 } else { 1 };
+  let mut trits = vec![];
+  let mut carry = 0;
+  for d in n.magnitude().to_radix_le(3) {
+    let v = d as i64 + carry;
+    let t = match v {
+      2 => -1,
+      3 => 0,
+      t => t,
+    };
+    carry = (v - t) / 3;
+    trits.push(t * sign);
+  }
+  if carry != 0 {
+    trits.push(sign);
+  }
+  // `to_radix_le` writes `0` as a digit
+  while trits.last() == Some(&0) {
+    trits.pop();
+  }
+  trits
+}
+
+/// balanced trits on both directions, lowest first from the last fractional digit
+fn value_trits(value: &DualBalancedTernary) -> (Vec<i64>, Vec<i64>) {
+  let coords: Vec<ComplexXy> = value.fractional.iter().rev().chain(&value.integral).map(|d| (*d).into()).collect();
+  let xs = coords.iter().map(|c| c.x as i64).collect();
+  let ys = coords.iter().map(|c| c.y as i64).collect();
+  (xs, ys)
+}
+
+/// value of balanced trits, lowest first, as the difference of positive trits and negative trits
+fn trits_value(trits: &[i64]) -> BigInt {
+  let part = |sign: i64| {
+    let digits: Vec<u8> = trits.iter().map(|t| u8::from(*t == sign)).collect();
+    BigUint::from_radix_le(&digits, 3).unwrap_or_default()
+  };
+  BigInt::from(part(1)) - BigInt::from(part(-1))
+}
+
+impl DualBalancedTernary {
+  /// exact `(x, y, k)` meaning `(x * 3^-k, y * 3^-k)`, with the smallest `k`, like `to_scaled_integers`
+  pub fn to_scaled_bigints(&self) -> (BigInt, BigInt, u32) {
+    let (xs, ys) = value_trits(self);
+    (trits_value(&xs), trits_value(&ys), self.fractional.len() as u32)
+  }
+
+  /// exact value of `(x * 3^-k, y * 3^-k)`, reverse of `to_scaled_bigints`
+  pub fn from_scaled_bigints(x: &BigInt, y: &BigInt, k: u32) -> Self {
+    Self::from_trits(&balanced_trits(x), &balanced_trits(y), k as usize)
+  }
+}
+
+/// exact value on `1` direction
+impl From<BigInt> for DualBalancedTernary {
+  fn from(n: BigInt) -> Self {
+    Self::from_scaled_bigints(&BigInt::default(), &n, 0)
+  }
+}
+
+/// exact value from `(x, y)`, like `DualBalancedTernary::new`
+impl From<(BigInt, BigInt)> for DualBalancedTernary {
+  fn from(pair: (BigInt, BigInt)) -> Self {
+    Self::from_scaled_bigints(&pair.0, &pair.1, 0)
+  }
+}
+
+/// fails for fractional digits, or digits on `3` direction
+impl TryFrom<DualBalancedTernary> for BigInt {
+  type Error = DbtError;
+  fn try_from(value: DualBalancedTernary) -> Result<Self, Self::Error> {
+    let (x, y) = <(BigInt, BigInt)>::try_from(value)?;
+    if x.sign() == Sign::NoSign {
+      Ok(y)
+    } else {
+      Err(DbtError::NonLinearValue)
+    }
+  }
+}
+
+/// fails for fractional digits
+impl TryFrom<DualBalancedTernary> for (BigInt, BigInt) {
+  type Error = DbtError;
+  fn try_from(value: DualBalancedTernary) -> Result<Self, Self::Error> {
+    if !value.is_integral() {
+      return Err(DbtError::NonIntegralValue);
+    }
+    let (x, y, _) = value.to_scaled_bigints();
+    Ok((x, y))
+  }
+}
@@ -0,0 +1,58 @@
+//! Check digit for DBT values typed by hand, in the way of Damm algorithm.
+//!
+//! Digits are mapped to `0..9` with `5` as `0`, and folded by `x * y = (2x + y) mod 9`,
+//! a quasigroup where `(c * x) * y = (c * y) * x` only when `x = y`, so a single wrong digit
+//! or a swap of neighbouring digits always changes the result. The fold starts from the number of
+//! integral digits to cover the radix point, and the check digit brings the result back to `5`.
+
+use std::str::FromStr;
+
+use crate::error::DbtError;
+use crate::primes::DualBalancedTernary;
+
+fn fold(interim: u32, digit: u32) -> u32 {
+  (2 * interim + digit) % 9
+}
+
+/// `None` for characters other than digits and `.`
+fn check_value(s: &str) -> Option<u32> {
+  let int_len = s.chars().take_while(|c| *c != '.').count() as u32;
+  let mut interim = int_len % 9;
+  for c in s.chars().filter(|c| *c != '.') {
+    let d = c.to_digit(10).filter(|d| *d > 0)?;
+    interim = fold(interim, (d + 4) % 9);
+  }
+  // `2 * interim + check = 0 (mod 9)`
+  Some((18 - 2 * interim) % 9)
+}
+
+/// digit character for a check value, `0` is `5`
+fn check_digit(value: u32) -> char {
+  std::char::from_digit((value + 4) % 9 + 1, 10).unwrap_or('5')
+}
+
+impl DualBalancedTernary {
+  /// `Display` form with a check digit, like `&3445647.674-8`
+  pub fn format_with_check(&self) -> String {
+    let s = format!("{}", self);
+    match check_value(&s[1..]) {
+      Some(v) => format!("{}-{}", s, check_digit(v)),
+      None => unreachable!("display form has only digits: {}", s),
+    }
+  }
+
+  /// reads the form of `format_with_check`, rejects the value when the check digit does not match
+  pub fn parse_checked(s: &str) -> Result<Self, DbtError> {
+    let (body, check) = s.rsplit_once('-').ok_or(DbtError::MissingCheckDigit)?;
+    let value = DualBalancedTernary::from_str(body)?;
+    let mut check_chars = check.chars();
+    let found = match (check_chars.next(), check_chars.next()) {
+      (Some(c), None) => c,
+      _ => return Err(DbtError::MissingCheckDigit),
+    };
+    match check_value(&body[1..]) {
+      Some(v) if check_digit(v) == found => Ok(value),
+      _ => Err(DbtError::CheckDigitMismatch),
+    }
+  }
+}
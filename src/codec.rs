@@ -0,0 +1,290 @@
+//! Binary formats for DBT values.
+//!
+//! Nibble format, version `1`, written by `TryFrom<DualBalancedTernary> for Vec<u8>`:
+//!
+//! ```text
+//! [0xDB 0x01]+[integral length]+[fractional length]+[digit pairs]
+//! ```
+//!
+//! lengths are LEB128 varints, digits take a nibble each, integral digits from unit position then fractional digits,
+//! the first digit at the high nibble, and a `5` nibble is padded at the end for odd counts.
+//!
+//! Packed format, 5 digits in every `u16` since `9^5 < 65536`:
+//!
+//! ```text
+//! [0xDB 0xFE]+[integral length]+[fractional length]+[u16 groups]
+//! ```
+//!
+//! lengths are LEB128 varints, groups are little endian and hold integral digits then fractional digits,
+//! each digit taking `digit - 1` with the first digit as the lowest. The last group is padded with `5`s.
+//!
+//! Legacy buffers have a single `u8` integral length then nibble pairs of `1`-`9`.
+//! A length of 219 makes them start with `0xDB` as well, so the formats are told apart by the second byte,
+//! which is never `0x01` or `0xFE` for a digit pair.
+//! Legacy buffers can not be written anymore, but still get decoded.
+
+use std::convert::TryFrom;
+
+use crate::digit::{DualBalancedTernaryDigit, DualBalancedTernaryDigit::*};
+use crate::error::DbtError;
+use crate::primes::DualBalancedTernary;
+
+/// first bytes of the nibble format, the second byte is the version
+pub const NIBBLE_HEADER: [u8; 2] = [0xDB, 0x01];
+
+/// first bytes of the packed format
+pub const PACKED_MAGIC: [u8; 2] = [0xDB, 0xFE];
+
+const DIGITS_PER_GROUP: usize = 5;
+/// `9^5`, groups are always smaller
+const GROUP_LIMIT: u16 = 59049;
+
+pub(crate) fn write_varint(buf: &mut Vec<u8>, n: usize) {
+  let mut n = n;
+  loop {
+    let byte = (n & 0x7f) as u8;
+    n >>= 7;
+    if n == 0 {
+      buf.push(byte);
+      return;
+    }
+    buf.push(byte | 0x80);
+  }
+}
+
+/// LEB128 value starting at `offset`, returned with the offset after it
+pub(crate) fn read_varint(buf: &[u8], offset: usize) -> Result<(usize, usize), DbtError> {
+  let mut n: usize = 0;
+  let mut shift = 0;
+  let mut idx = offset;
+  loop {
+    let byte = *buf.get(idx).ok_or(DbtError::BufferTooShort)?;
+    let bits = (byte & 0x7f) as usize;
+    if shift >= usize::BITS || (bits << shift) >> shift != bits {
+      return Err(DbtError::InvalidVarint(offset));
+    }
+    n |= bits << shift;
+    idx += 1;
+    if byte & 0x80 == 0 {
+      return Ok((n, idx));
+    }
+    shift += 7;
+  }
+}
+
+/// encode into the packed format, `5`s at both ends are not written
+pub fn encode_packed(value: &DualBalancedTernary) -> Vec<u8> {
+  let mut buf = PACKED_MAGIC.to_vec();
+  write_varint(&mut buf, value.integral.len());
+  write_varint(&mut buf, value.fractional.len());
+  for group in value
+    .integral
+    .iter()
+    .chain(value.fractional.iter())
+    .collect::<Vec<_>>()
+    .chunks(DIGITS_PER_GROUP)
+  {
+    let mut n: u16 = 0;
+    for d in (0..DIGITS_PER_GROUP).rev() {
+      let digit = group.get(d).map_or(Dbt5, |x| **x);
+      n = n * 9 + (u8::from(digit) - 1) as u16;
+    }
+    buf.extend_from_slice(&n.to_le_bytes());
+  }
+  buf
+}
+
+/// decode one value in the packed format from the start of `buf`, returned with the number of bytes it takes
+pub(crate) fn decode_packed_prefix(buf: &[u8]) -> Result<(DualBalancedTernary, usize), DbtError> {
+  if buf.len() < PACKED_MAGIC.len() {
+    return Err(DbtError::BufferTooShort);
+  }
+  if buf[..PACKED_MAGIC.len()] != PACKED_MAGIC {
+    return Err(DbtError::UnknownFormat);
+  }
+  let (int_len, offset) = read_varint(buf, PACKED_MAGIC.len())?;
+  let (frac_len, offset) = read_varint(buf, offset)?;
+  let total = int_len.checked_add(frac_len).ok_or(DbtError::BufferTooShort)?;
+  let groups = total.div_ceil(DIGITS_PER_GROUP);
+  let end = groups
+    .checked_mul(2)
+    .and_then(|n| n.checked_add(offset))
+    .ok_or(DbtError::BufferTooShort)?;
+  if buf.len() < end {
+    return Err(DbtError::BufferTooShort);
+  }
+
+  let mut digits: Vec<DualBalancedTernaryDigit> = Vec::with_capacity(groups * DIGITS_PER_GROUP);
+  for idx in 0..groups {
+    let at = offset + idx * 2;
+    let mut n = u16::from_le_bytes([buf[at], buf[at + 1]]);
+    if n >= GROUP_LIMIT {
+      return Err(DbtError::InvalidPackedGroup(n));
+    }
+    for _ in 0..DIGITS_PER_GROUP {
+      digits.push(DualBalancedTernaryDigit::try_from((n % 9) as u8 + 1)?);
+      n /= 9;
+    }
+  }
+  digits.truncate(total);
+  let fractional = digits.split_off(int_len);
+  Ok((DualBalancedTernary::from_digits(digits, fractional), end))
+}
+
+/// decode one value in the packed format, bytes after it are not read
+pub fn decode_packed(buf: &[u8]) -> Result<DualBalancedTernary, DbtError> {
+  decode_packed_prefix(buf).map(|(v, _)| v)
+}
+
+/// encode into the nibble format, `5`s at both ends are not written
+pub fn encode_nibbles(value: &DualBalancedTernary) -> Vec<u8> {
+  let mut buf = NIBBLE_HEADER.to_vec();
+  write_varint(&mut buf, value.integral.len());
+  write_varint(&mut buf, value.fractional.len());
+  let digits: Vec<u8> = value.integral.iter().chain(value.fractional.iter()).map(|d| u8::from(*d)).collect();
+  for pair in digits.chunks(2) {
+    buf.push((pair[0] << 4) + pair.get(1).unwrap_or(&5));
+  }
+  buf
+}
+
+/// decode one value in the nibble format from the start of `buf`, returned with the number of bytes it takes
+pub(crate) fn decode_nibbles_prefix(buf: &[u8]) -> Result<(DualBalancedTernary, usize), DbtError> {
+  if buf.len() < NIBBLE_HEADER.len() {
+    return Err(DbtError::BufferTooShort);
+  }
+  if buf[..NIBBLE_HEADER.len()] != NIBBLE_HEADER {
+    return Err(DbtError::UnknownFormat);
+  }
+  let (int_len, offset) = read_varint(buf, NIBBLE_HEADER.len())?;
+  let (frac_len, offset) = read_varint(buf, offset)?;
+  let total = int_len.checked_add(frac_len).ok_or(DbtError::BufferTooShort)?;
+  let end = total.div_ceil(2).checked_add(offset).ok_or(DbtError::BufferTooShort)?;
+  if buf.len() < end {
+    return Err(DbtError::BufferTooShort);
+  }
+
+  let mut digits: Vec<DualBalancedTernaryDigit> = Vec::with_capacity(total);
+  for idx in 0..total {
+    let x = buf[offset + idx / 2];
+    let nibble = if idx % 2 == 0 { x >> 4 } else { x & 0b00001111 };
+    digits.push(DualBalancedTernaryDigit::try_from(nibble)?);
+  }
+  let fractional = digits.split_off(int_len);
+  Ok((DualBalancedTernary::from_digits(digits, fractional), end))
+}
+
+/// buffer format of older versions, without header
+/// [integral length]+[integral pairs]+[fractional pairs]
+fn decode_legacy(buf: &[u8]) -> Result<DualBalancedTernary, DbtError> {
+  if buf.is_empty() {
+    return Err(DbtError::BufferTooShort);
+  }
+
+  let int_range = (buf[0] as usize).div_ceil(2);
+
+  if buf.len() < (int_range + 1) {
+    return Err(DbtError::BufferTooShort);
+  }
+  let mut integral: Vec<DualBalancedTernaryDigit> = vec![];
+  let mut fractional: Vec<DualBalancedTernaryDigit> = vec![];
+
+  for (idx, x) in buf.iter().enumerate().skip(1) {
+    if idx < int_range + 1 {
+      integral.push(DualBalancedTernaryDigit::try_from((x & 0b11110000) >> 4)?);
+      integral.push(DualBalancedTernaryDigit::try_from(x & 0b00001111)?);
+    } else {
+      fractional.push(DualBalancedTernaryDigit::try_from((x & 0b11110000) >> 4)?);
+      fractional.push(DualBalancedTernaryDigit::try_from(x & 0b00001111)?);
+    }
+  }
+
+  Ok(DualBalancedTernary::from_digits(integral, fractional))
+}
+
+/// bytes of digits after the lengths, for a header and lengths in nibble or packed format
+pub(crate) fn body_length(header: &[u8], int_len: usize, frac_len: usize) -> Result<usize, DbtError> {
+  let total = int_len.checked_add(frac_len).ok_or(DbtError::BufferTooShort)?;
+  if header == NIBBLE_HEADER {
+    Ok(total.div_ceil(2))
+  } else if header == PACKED_MAGIC {
+    Ok(total.div_ceil(DIGITS_PER_GROUP) * 2)
+  } else {
+    Err(DbtError::UnknownFormat)
+  }
+}
+
+/// decode one value in nibble or packed format from the start of `buf`, returned with the number of bytes it takes,
+/// so values written one after another can be read from a borrowed slice
+pub fn decode_prefix(buf: &[u8]) -> Result<(DualBalancedTernary, usize), DbtError> {
+  if buf.starts_with(&NIBBLE_HEADER) {
+    decode_nibbles_prefix(buf)
+  } else if buf.starts_with(&PACKED_MAGIC) {
+    decode_packed_prefix(buf)
+  } else if buf.len() < NIBBLE_HEADER.len() {
+    Err(DbtError::BufferTooShort)
+  } else {
+    Err(DbtError::UnknownFormat)
+  }
+}
+
+/// decode a buffer in any format, told apart by its header, bytes after a nibble or packed value are not read
+pub fn decode(buf: &[u8]) -> Result<DualBalancedTernary, DbtError> {
+  if buf.starts_with(&NIBBLE_HEADER) || buf.starts_with(&PACKED_MAGIC) {
+    decode_prefix(buf).map(|(v, _)| v)
+  } else {
+    decode_legacy(buf)
+  }
+}
+
+/// decode a nibble or packed buffer only when it is exactly what the encoder writes for the value,
+/// no padded `5`s, no longer varints, no other padding nibbles, and nothing after the value.
+/// the error holds the offset of the first byte that differs
+pub fn decode_strict(buf: &[u8]) -> Result<DualBalancedTernary, DbtError> {
+  let (value, _) = decode_prefix(buf).map_err(|e| DbtError::NonCanonical {
+    offset: failure_offset(buf, e),
+  })?;
+  let canonical = if buf.starts_with(&NIBBLE_HEADER) {
+    encode_nibbles(&value)
+  } else {
+    encode_packed(&value)
+  };
+  match canonical.iter().zip(buf).position(|(a, b)| a != b) {
+    Some(offset) => Err(DbtError::NonCanonical { offset }),
+    None if canonical.len() != buf.len() => Err(DbtError::NonCanonical {
+      offset: canonical.len().min(buf.len()),
+    }),
+    None => Ok(value),
+  }
+}
+
+/// offset of the byte `decode_prefix` failed at
+fn failure_offset(buf: &[u8], e: DbtError) -> usize {
+  match buf {
+    [] => return 0,
+    [first, ..] if *first != NIBBLE_HEADER[0] => return 0,
+    [_] => return 1,
+    [_, second, ..] if *second != NIBBLE_HEADER[1] && *second != PACKED_MAGIC[1] => return 1,
+    _ => {}
+  }
+  if let DbtError::InvalidVarint(offset) = e {
+    return offset;
+  }
+  let body = read_varint(buf, 2).and_then(|(_, offset)| read_varint(buf, offset));
+  let offset = match body {
+    Ok((_, offset)) => offset,
+    Err(_) => return buf.len(),
+  };
+  let invalid_nibble = |n: u8| DualBalancedTernaryDigit::try_from(n).is_err();
+  let bad = match e {
+    DbtError::InvalidDigitValue(_) => buf[offset..]
+      .iter()
+      .position(|x| invalid_nibble(x >> 4) || invalid_nibble(x & 0b00001111)),
+    DbtError::InvalidPackedGroup(_) => buf[offset..]
+      .chunks(2)
+      .position(|pair| pair.len() == 2 && u16::from_le_bytes([pair[0], pair[1]]) >= GROUP_LIMIT)
+      .map(|idx| idx * 2),
+    _ => None,
+  };
+  bad.map_or(buf.len(), |idx| offset + idx)
+}
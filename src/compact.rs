@@ -0,0 +1,113 @@
+//! Compact text form of DBT values, a pair of digits takes one character, or two for 17 of the 81 pairs.
+//!
+//! Integral digits are paired from the unit position and fractional digits from the radix point,
+//! with `5` padded at the outer ends, so `&3445647.674` is read as `53` `44` `56` `47` `.` `67` `45`.
+//! A pair `ab` takes index `(a - 1) * 9 + (b - 1)`, `.` marks the radix point.
+//!
+//! Only the unreserved characters of RFC 3986, `A-Z a-z 0-9 - . _ ~`, are used, so the text goes into URLs unescaped.
+//! That leaves 64 characters besides `.` and `~`, fewer than the 81 pairs, so this is not a plain base-81 form:
+//! indexes below 64 take one character of the alphabet, and the other 17 pairs, from `82` to `99`,
+//! are written as `~` followed by the character of `index - 64`.
+//!
+//! In the worst case every pair is escaped, so `n` integral and `m` fractional digits take at most
+//! `2 * ceil(n / 2) + 2 * ceil(m / 2)` characters, plus one for `.` when there are fractional digits.
+//! Pairs padded with `5` at the top of integral part are never escaped, so the text is never longer than the `&` form.
+
+use crate::digit::{DualBalancedTernaryDigit, DualBalancedTernaryDigit::*};
+use crate::error::DbtError;
+use crate::primes::DualBalancedTernary;
+
+use std::convert::TryFrom;
+
+const ALPHABET: &[u8; 64] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz-_";
+
+/// starts the two characters of a pair with index from 64
+const ESCAPE: char = '~';
+
+fn push_pair(s: &mut String, a: DualBalancedTernaryDigit, b: DualBalancedTernaryDigit) {
+  let idx = (u8::from(a) as usize - 1) * 9 + u8::from(b) as usize - 1;
+  if idx < ALPHABET.len() {
+    s.push(ALPHABET[idx] as char);
+  } else {
+    s.push(ESCAPE);
+    s.push(ALPHABET[idx - ALPHABET.len()] as char);
+  }
+}
+
+/// `escaped` for the character after `~`, which only takes the first 17 characters
+fn char_pair(c: char, escaped: bool) -> Option<(DualBalancedTernaryDigit, DualBalancedTernaryDigit)> {
+  let mut idx = ALPHABET.iter().position(|x| *x as char == c)? as u8;
+  if escaped {
+    idx += ALPHABET.len() as u8;
+  }
+  let a = DualBalancedTernaryDigit::try_from(idx / 9 + 1).ok()?;
+  let b = DualBalancedTernaryDigit::try_from(idx % 9 + 1).ok()?;
+  Some((a, b))
+}
+
+impl DualBalancedTernary {
+  /// one character for most pairs of digits and `~` with one more for the others, without `&`,
+  /// `&5` is written as `e` for the pair `55`
+  pub fn to_compact_string(&self) -> String {
+    let mut s = String::new();
+    let mut integral = self.integral.to_owned();
+    if integral.is_empty() && self.fractional.is_empty() {
+      integral.push(Dbt5);
+    }
+    if integral.len() % 2 == 1 {
+      integral.push(Dbt5);
+    }
+    for pair in integral.chunks(2).rev() {
+      push_pair(&mut s, pair[1], pair[0]);
+    }
+    if !self.fractional.is_empty() {
+      s.push('.');
+      for pair in self.fractional.chunks(2) {
+        push_pair(&mut s, pair[0], *pair.get(1).unwrap_or(&Dbt5));
+      }
+    }
+    s
+  }
+
+  /// reads the form of `to_compact_string`, `position` in errors counts characters from the start
+  pub fn from_compact_str(s: &str) -> Result<Self, DbtError> {
+    if s.is_empty() {
+      return Err(DbtError::EmptyNumber);
+    }
+    let mut integral: Vec<DualBalancedTernaryDigit> = vec![];
+    let mut fractional: Vec<DualBalancedTernaryDigit> = vec![];
+    let mut in_fractional = false;
+    let mut escaped = false;
+    for (idx, c) in s.chars().enumerate() {
+      if c == '.' && !escaped {
+        if in_fractional {
+          return Err(DbtError::TooManyRadixPoints);
+        }
+        in_fractional = true;
+        continue;
+      }
+      if c == ESCAPE && !escaped {
+        escaped = true;
+        continue;
+      }
+      let (a, b) = char_pair(c, escaped).ok_or(DbtError::InvalidDigit { ch: c, position: idx })?;
+      escaped = false;
+      if in_fractional {
+        fractional.push(a);
+        fractional.push(b);
+      } else {
+        integral.push(a);
+        integral.push(b);
+      }
+    }
+    if escaped {
+      return Err(DbtError::InvalidDigit {
+        ch: ESCAPE,
+        position: s.chars().count() - 1,
+      });
+    }
+    // pairs were read from the highest digit
+    integral.reverse();
+    Ok(DualBalancedTernary::from_digits(integral, fractional))
+  }
+}
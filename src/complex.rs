@@ -1,12 +1,161 @@
+//! Plain complex numbers for DBT values, `x` is on the `3` direction and `y` on the `1` direction.
+//!
+//! As a complex number, a value is `y + x * i`, so `&3` is `i` and `&3 * &3` is `&9`, which is `-1`.
+//! With `num-complex` feature, `Complex` converts with `re` for `y` and `im` for `x`.
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
 /// simple complex number struct
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
 pub struct ComplexXy {
   pub x: f64,
   pub y: f64,
 }
 
 impl ComplexXy {
+  pub fn new(x: f64, y: f64) -> Self {
+    ComplexXy { x, y }
+  }
+
   pub fn flip_xy(&self) -> ComplexXy {
     ComplexXy { x: self.y, y: self.x }
   }
+
+  /// negates `x`, like `DualBalancedTernary::conjugate`
+  pub fn conjugate(&self) -> ComplexXy {
+    ComplexXy { x: -self.x, y: self.y }
+  }
+
+  /// `x * x + y * y`, the norm of Gaussian integers
+  pub fn norm_sqr(&self) -> f64 {
+    self.x * self.x + self.y * self.y
+  }
+
+  /// distance to zero
+  pub fn norm(&self) -> f64 {
+    self.x.hypot(self.y)
+  }
+
+  /// `|x| + |y|`
+  pub fn l1_norm(&self) -> f64 {
+    self.x.abs() + self.y.abs()
+  }
+
+  pub fn scale(&self, t: f64) -> ComplexXy {
+    ComplexXy {
+      x: self.x * t,
+      y: self.y * t,
+    }
+  }
+}
+
+impl From<(f64, f64)> for ComplexXy {
+  fn from(pair: (f64, f64)) -> Self {
+    ComplexXy { x: pair.0, y: pair.1 }
+  }
+}
+
+impl From<ComplexXy> for (f64, f64) {
+  fn from(c: ComplexXy) -> Self {
+    (c.x, c.y)
+  }
+}
+
+impl Add for ComplexXy {
+  type Output = Self;
+  fn add(self, other: Self) -> Self {
+    ComplexXy {
+      x: self.x + other.x,
+      y: self.y + other.y,
+    }
+  }
+}
+
+impl Sub for ComplexXy {
+  type Output = Self;
+  fn sub(self, other: Self) -> Self {
+    ComplexXy {
+      x: self.x - other.x,
+      y: self.y - other.y,
+    }
+  }
+}
+
+impl Neg for ComplexXy {
+  type Output = Self;
+  fn neg(self) -> Self {
+    ComplexXy { x: -self.x, y: -self.y }
+  }
+}
+
+impl Mul for ComplexXy {
+  type Output = Self;
+  fn mul(self, other: Self) -> Self {
+    ComplexXy {
+      x: self.y * other.x + self.x * other.y,
+      y: self.y * other.y - self.x * other.x,
+    }
+  }
+}
+
+/// follows `f64`, dividing by zero gives `NaN` or infinite values
+impl Div for ComplexXy {
+  type Output = Self;
+  fn div(self, other: Self) -> Self {
+    (self * other.conjugate()).scale(1.0 / other.norm_sqr())
+  }
+}
+
+#[cfg(feature = "num-complex")]
+mod interop {
+  use std::convert::TryFrom;
+
+  use num_complex::Complex;
+
+  use super::ComplexXy;
+  use crate::error::DbtError;
+  use crate::primes::DualBalancedTernary;
+
+  impl From<ComplexXy> for Complex<f64> {
+    fn from(c: ComplexXy) -> Self {
+      Complex::new(c.y, c.x)
+    }
+  }
+
+  impl From<Complex<f64>> for ComplexXy {
+    fn from(c: Complex<f64>) -> Self {
+      ComplexXy { x: c.im, y: c.re }
+    }
+  }
+
+  impl From<DualBalancedTernary> for Complex<f64> {
+    fn from(value: DualBalancedTernary) -> Self {
+      ComplexXy::from(value).into()
+    }
+  }
+
+  /// like `DualBalancedTernary::try_new`, digits are limited by `DIV_PRECISION`
+  impl TryFrom<Complex<f64>> for DualBalancedTernary {
+    type Error = DbtError;
+    fn try_from(c: Complex<f64>) -> Result<Self, Self::Error> {
+      DualBalancedTernary::try_new(c.im, c.re)
+    }
+  }
+
+  impl From<Complex<i64>> for DualBalancedTernary {
+    fn from(c: Complex<i64>) -> Self {
+      DualBalancedTernary::from_integer_xy(c.im as i128, c.re as i128)
+    }
+  }
+
+  /// exact, fails for fractional digits or parts out of `i64`
+  impl TryFrom<DualBalancedTernary> for Complex<i64> {
+    type Error = DbtError;
+    fn try_from(value: DualBalancedTernary) -> Result<Self, Self::Error> {
+      let (x, y) = value.integer_xy()?;
+      let im = i64::try_from(x).map_err(|_| DbtError::IntegerOverflow)?;
+      let re = i64::try_from(y).map_err(|_| DbtError::IntegerOverflow)?;
+      Ok(Complex::new(re, im))
+    }
+  }
 }
@@ -0,0 +1,120 @@
+//! Precision and rounding settings for arithmetic that can not be exact.
+//!
+//! Operators like `/` always use `DIV_PRECISION`, methods ending with `_with` take an `ArithmeticContext` instead,
+//! so different parts of a program can pick their own precision.
+
+use std::convert::TryFrom;
+
+use crate::digit::DualBalancedTernaryDigit::{self, *};
+use crate::error::DbtError;
+use crate::primes::{DualBalancedTernary, DIV_PRECISION};
+
+/// how to handle digits beyond precision, `1` direction and `3` direction are rounded separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RoundingMode {
+  /// drop the digits, in balanced ternary this already gives the nearest value
+  #[default]
+  Nearest,
+  /// round toward negative side on each axis, i.e. toward `9` and `7`
+  Floor,
+  /// round toward positive side on each axis, i.e. toward `1` and `3`
+  Ceil,
+}
+
+/// settings for inexact arithmetic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArithmeticContext {
+  /// how many digits in fractional part are kept
+  pub precision: usize,
+  pub rounding: RoundingMode,
+}
+
+impl Default for ArithmeticContext {
+  fn default() -> Self {
+    ArithmeticContext {
+      precision: DIV_PRECISION,
+      rounding: RoundingMode::Nearest,
+    }
+  }
+}
+
+impl ArithmeticContext {
+  pub fn new(precision: usize, rounding: RoundingMode) -> Self {
+    ArithmeticContext { precision, rounding }
+  }
+
+  /// a context with given precision and `RoundingMode::Nearest`
+  pub fn with_precision(precision: usize) -> Self {
+    ArithmeticContext {
+      precision,
+      rounding: RoundingMode::Nearest,
+    }
+  }
+
+  /// position of the last kept digit
+  fn last_position(&self) -> i64 {
+    -(self.precision as i64)
+  }
+
+  /// applies rounding to a value that has been cut at precision,
+  /// `y_sign` and `x_sign` are signs of the dropped parts on `1` and `3` directions
+  fn adjust(&self, value: DualBalancedTernary, y_sign: i64, x_sign: i64) -> DualBalancedTernary {
+    let step = |sign: i64| match self.rounding {
+      RoundingMode::Nearest => 0,
+      RoundingMode::Floor => sign.min(0),
+      RoundingMode::Ceil => sign.max(0),
+    };
+    let (y_step, x_step) = (step(y_sign), step(x_sign));
+    match DualBalancedTernaryDigit::try_from((x_step, y_step)) {
+      Ok(Dbt5) | Err(_) => value,
+      Ok(d) => value.add_at(self.last_position(), d),
+    }
+  }
+}
+
+impl DualBalancedTernary {
+  /// keeps `ctx.precision` digits in fractional part, the dropped digits are handled by `ctx.rounding`
+  pub fn round_with(&self, ctx: &ArithmeticContext) -> DualBalancedTernary {
+    let kept = self.round_n(ctx.precision);
+    let (tail_x, tail_y) = (self - &kept).split_yx();
+    // `split_yx` keeps x part in 3 direction, turn it to 1 direction for reading its sign
+    ctx.adjust(kept, tail_y.linear_sign(), tail_x.rotate7().linear_sign())
+  }
+
+  /// multiply and then round to `ctx.precision` digits in fractional part
+  pub fn mul_with(&self, other: &DualBalancedTernary, ctx: &ArithmeticContext) -> DualBalancedTernary {
+    (self * other).round_with(ctx)
+  }
+
+  /// divide with a digit settled for every position down to `ctx.precision` digits in fractional part,
+  /// and round by the reminder
+  pub fn div_with(&self, other: &DualBalancedTernary, ctx: &ArithmeticContext) -> Result<DualBalancedTernary, DbtError> {
+    if other.is_zero() {
+      return Err(DbtError::DivisionByZero);
+    }
+    let cj = other.conjugate();
+    let a2 = self * &cj;
+    // norm of divisor, it's positive and only contains 1,5,9
+    let b2 = other * cj;
+    let (ax, ay) = a2.split_yx();
+    let (qy, ry) = ay.linear_divide_to(&b2, ctx.last_position())?;
+    let (qx, rx) = ax.rotate7().linear_divide_to(&b2, ctx.last_position())?;
+    Ok(ctx.adjust(qy + qx.rotate3(), ry.linear_sign(), rx.linear_sign()))
+  }
+
+  /// convert from a float on the `3` direction, like `TryFrom<f64>`, with `ctx.precision` digits in fractional part
+  pub fn from_f64_with(x: f64, ctx: &ArithmeticContext) -> Result<DualBalancedTernary, DbtError> {
+    let (result, dropped) = DualBalancedTernary::from_f64_digits(x, ctx.precision)?;
+    // digits are taken with `floor`, so the dropped part is never negative
+    let round_up = match ctx.rounding {
+      RoundingMode::Nearest => dropped > 0.5,
+      RoundingMode::Floor => false,
+      RoundingMode::Ceil => dropped > 0.0,
+    };
+    if round_up {
+      Ok(result.add_at(ctx.last_position(), Dbt3))
+    } else {
+      Ok(result)
+    }
+  }
+}
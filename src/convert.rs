@@ -0,0 +1,209 @@
+//! Exact conversions between DBT integers and primitive integers.
+//!
+//! Integers are placed on `1` direction, which is the real axis of the ring,
+//! pairs follow `DualBalancedTernary::new` with `(x, y)` for `3` and `1` directions.
+
+use std::convert::TryFrom;
+
+use crate::complex::ComplexXy;
+use crate::digit::DualBalancedTernaryDigit;
+use crate::error::DbtError;
+use crate::primes::DualBalancedTernary;
+
+/// negative flag and magnitude of an integer on one direction
+type Magnitude = (bool, u128);
+
+/// balanced ternary trits of `-magnitude` or `magnitude`, lowest first
+fn balanced_trits(negative: bool, magnitude: u128) -> Vec<i64> {
+  let sign = if negative { -1 } else { 1 };
+  let mut trits = vec![];
+  let mut n = magnitude;
+  while n != 0 {
+    match n % 3 {
+      0 => trits.push(0),
+      1 => trits.push(sign),
+      _ => {
+        trits.push(-sign);
+        // `(n + 1) / 3` without overflowing at `u128::MAX`
+        n = n / 3 + 1;
+        continue;
+      }
+    }
+    n /= 3;
+  }
+  trits
+}
+
+/// sign and magnitude from balanced trits, highest first.
+/// prefixes of balanced ternary keep the sign of the leading trit, so magnitude never goes below zero
+fn trits_magnitude(trits: impl Iterator<Item = i64>) -> Result<Magnitude, DbtError> {
+  let mut sign = 0;
+  let mut magnitude: u128 = 0;
+  for t in trits {
+    if sign == 0 {
+      sign = t;
+    }
+    let next = magnitude.checked_mul(3).ok_or(DbtError::IntegerOverflow)?;
+    magnitude = match t * sign {
+      1 => next.checked_add(1).ok_or(DbtError::IntegerOverflow)?,
+      -1 => next - 1,
+      _ => next,
+    };
+  }
+  Ok((sign < 0, magnitude))
+}
+
+fn signed_value(part: Magnitude) -> Result<i128, DbtError> {
+  match part {
+    (true, m) => 0i128.checked_sub_unsigned(m).ok_or(DbtError::IntegerOverflow),
+    (false, m) => i128::try_from(m).map_err(|_| DbtError::IntegerOverflow),
+  }
+}
+
+impl DualBalancedTernary {
+  /// exact value from sign and magnitude on both directions
+  fn from_magnitudes(x: Magnitude, y: Magnitude) -> Self {
+    Self::from_trits(&balanced_trits(x.0, x.1), &balanced_trits(y.0, y.1), 0)
+  }
+
+  /// digits from balanced trits on both directions, lowest first, the first `shift` digits go to fractional part
+  pub(crate) fn from_trits(xs: &[i64], ys: &[i64], shift: usize) -> Self {
+    let mut digits = vec![];
+    for idx in 0..xs.len().max(ys.len()).max(shift) {
+      let pair = (*xs.get(idx).unwrap_or(&0), *ys.get(idx).unwrap_or(&0));
+      match DualBalancedTernaryDigit::try_from(pair) {
+        Ok(d) => digits.push(d),
+        Err(e) => unreachable!("balanced trits always form a digit: {}", e),
+      }
+    }
+    let integral = digits.split_off(shift);
+    digits.reverse();
+    DualBalancedTernary::from_digits(integral, digits)
+  }
+
+  /// sign and magnitude on both directions of a value without fractional digits
+  fn integer_magnitudes(&self) -> Result<(Magnitude, Magnitude), DbtError> {
+    if !self.is_integral() {
+      return Err(DbtError::NonIntegralValue);
+    }
+    let coords: Vec<ComplexXy> = self.integral.iter().rev().map(|d| (*d).into()).collect();
+    let x = trits_magnitude(coords.iter().map(|c| c.x as i64))?;
+    let y = trits_magnitude(coords.iter().map(|c| c.y as i64))?;
+    Ok((x, y))
+  }
+
+  /// exact value from integer coordinates, every digit is a pair of balanced trits
+  pub(crate) fn from_integer_xy(x: i128, y: i128) -> Self {
+    Self::from_magnitudes((x < 0, x.unsigned_abs()), (y < 0, y.unsigned_abs()))
+  }
+
+  /// exact `(x, y)` of a value without fractional digits
+  pub(crate) fn integer_xy(&self) -> Result<(i128, i128), DbtError> {
+    let (x, y) = self.integer_magnitudes()?;
+    Ok((signed_value(x)?, signed_value(y)?))
+  }
+
+  /// exact `(x, y, k)` meaning `(x * 3^-k, y * 3^-k)`, with the smallest `k`,
+  /// fails when coordinates do not fit in `i128`
+  pub fn to_scaled_integers(&self) -> Result<(i128, i128, u32), DbtError> {
+    let k = self.fractional.len();
+    let (x, y) = self.move_by(k as i64).integer_xy()?;
+    Ok((x, y, k as u32))
+  }
+
+  /// exact value of `(x * 3^-k, y * 3^-k)`, reverse of `to_scaled_integers`
+  pub fn from_scaled_integers(x: i128, y: i128, k: u32) -> Self {
+    Self::from_integer_xy(x, y).move_by(-(k as i64))
+  }
+}
+
+macro_rules! signed_conversions {
+  ($($t:ty),*) => {
+    $(
+      /// exact value on `1` direction
+      impl From<$t> for DualBalancedTernary {
+        fn from(n: $t) -> Self {
+          Self::from_magnitudes((false, 0), (n < 0, n.unsigned_abs() as u128))
+        }
+      }
+
+      /// exact value from `(x, y)`, like `DualBalancedTernary::new`
+      impl From<($t, $t)> for DualBalancedTernary {
+        fn from(pair: ($t, $t)) -> Self {
+          let (x, y) = pair;
+          Self::from_magnitudes((x < 0, x.unsigned_abs() as u128), (y < 0, y.unsigned_abs() as u128))
+        }
+      }
+
+      /// fails for fractional digits, digits on `3` direction, or values out of range
+      impl TryFrom<DualBalancedTernary> for $t {
+        type Error = DbtError;
+        fn try_from(value: DualBalancedTernary) -> Result<Self, Self::Error> {
+          match value.integer_magnitudes()? {
+            ((_, 0), y) => <$t>::try_from(signed_value(y)?).map_err(|_| DbtError::IntegerOverflow),
+            _ => Err(DbtError::NonLinearValue),
+          }
+        }
+      }
+
+      /// fails for fractional digits or coordinates out of range
+      impl TryFrom<DualBalancedTernary> for ($t, $t) {
+        type Error = DbtError;
+        fn try_from(value: DualBalancedTernary) -> Result<Self, Self::Error> {
+          let (x, y) = value.integer_magnitudes()?;
+          let x = <$t>::try_from(signed_value(x)?).map_err(|_| DbtError::IntegerOverflow)?;
+          let y = <$t>::try_from(signed_value(y)?).map_err(|_| DbtError::IntegerOverflow)?;
+          Ok((x, y))
+        }
+      }
+    )*
+  };
+}
+
+macro_rules! unsigned_conversions {
+  ($($t:ty),*) => {
+    $(
+      /// exact value on `1` direction
+      impl From<$t> for DualBalancedTernary {
+        fn from(n: $t) -> Self {
+          Self::from_magnitudes((false, 0), (false, n as u128))
+        }
+      }
+
+      /// exact value from `(x, y)`, like `DualBalancedTernary::new`
+      impl From<($t, $t)> for DualBalancedTernary {
+        fn from(pair: ($t, $t)) -> Self {
+          Self::from_magnitudes((false, pair.0 as u128), (false, pair.1 as u128))
+        }
+      }
+
+      /// fails for fractional digits, digits on `3` direction, or values out of range
+      impl TryFrom<DualBalancedTernary> for $t {
+        type Error = DbtError;
+        fn try_from(value: DualBalancedTernary) -> Result<Self, Self::Error> {
+          match value.integer_magnitudes()? {
+            ((_, 0), (false, m)) => <$t>::try_from(m).map_err(|_| DbtError::IntegerOverflow),
+            ((_, 0), _) => Err(DbtError::IntegerOverflow),
+            _ => Err(DbtError::NonLinearValue),
+          }
+        }
+      }
+
+      /// fails for fractional digits or coordinates out of range
+      impl TryFrom<DualBalancedTernary> for ($t, $t) {
+        type Error = DbtError;
+        fn try_from(value: DualBalancedTernary) -> Result<Self, Self::Error> {
+          let unsigned = |part: Magnitude| match part {
+            (true, _) => Err(DbtError::IntegerOverflow),
+            (false, m) => <$t>::try_from(m).map_err(|_| DbtError::IntegerOverflow),
+          };
+          let (x, y) = value.integer_magnitudes()?;
+          Ok((unsigned(x)?, unsigned(y)?))
+        }
+      }
+    )*
+  };
+}
+
+signed_conversions!(i8, i16, i32, i64, i128, isize);
+unsigned_conversions!(u8, u16, u32, u64, u128, usize);
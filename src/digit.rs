@@ -1,5 +1,6 @@
 //! Digits for DBT, with 9 values, `5` at center, `1` at front
 use crate::complex::ComplexXy;
+use crate::error::DbtError;
 
 use std::{
   convert::TryFrom,
@@ -231,7 +232,7 @@ impl Mul for DualBalancedTernaryDigit {
       },
       Dbt7 => (Dbt5, b.rotate7()),
       Dbt8 => match b {
-        Dbt1 => (Dbt1, Dbt8),
+        Dbt1 => (Dbt5, Dbt8),
         Dbt2 => (Dbt7, Dbt3),
         Dbt3 => (Dbt5, Dbt4),
         Dbt4 => (Dbt9, Dbt1),
@@ -249,7 +250,7 @@ impl Mul for DualBalancedTernaryDigit {
 // pub const fractional_base: i64 = 1 / 3;
 
 impl TryFrom<(i64, i64)> for DualBalancedTernaryDigit {
-  type Error = String;
+  type Error = DbtError;
 
   fn try_from(value: (i64, i64)) -> Result<Self, Self::Error> {
     let (x, y) = value;
@@ -258,21 +259,21 @@ impl TryFrom<(i64, i64)> for DualBalancedTernaryDigit {
         -1 => Ok(Dbt2),
         0 => Ok(Dbt7),
         1 => Ok(Dbt6),
-        _ => Err(format!("unexpected y: {}", y)),
+        _ => Err(DbtError::InvalidCoordinate { x, y }),
       },
       0 => match y {
         -1 => Ok(Dbt9),
         0 => Ok(Dbt5),
         1 => Ok(Dbt1),
-        _ => Err(format!("unexpected y: {}", y)),
+        _ => Err(DbtError::InvalidCoordinate { x, y }),
       },
       1 => match y {
-        -1 => Ok(Dbt8),
+        -1 => Ok(Dbt4),
         0 => Ok(Dbt3),
-        1 => Ok(Dbt4),
-        _ => Err(format!("unexpected y: {}", y)),
+        1 => Ok(Dbt8),
+        _ => Err(DbtError::InvalidCoordinate { x, y }),
       },
-      _ => Err(format!("unexpected x: {}", x)),
+      _ => Err(DbtError::InvalidCoordinate { x, y }),
     }
   }
 }
@@ -333,7 +334,7 @@ impl From<DualBalancedTernaryDigit> for ComplexXy {
 }
 
 impl TryFrom<u8> for DualBalancedTernaryDigit {
-  type Error = String;
+  type Error = DbtError;
   fn try_from(x: u8) -> Result<Self, Self::Error> {
     match x {
       1 => Ok(Dbt1),
@@ -345,7 +346,7 @@ impl TryFrom<u8> for DualBalancedTernaryDigit {
       7 => Ok(Dbt7),
       8 => Ok(Dbt8),
       9 => Ok(Dbt9),
-      _ => Err(format!("unknown digit for dbt: {}", x)),
+      _ => Err(DbtError::InvalidDigitValue(x)),
     }
   }
 }
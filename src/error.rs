@@ -0,0 +1,99 @@
+//! Errors for parsing, converting and decoding DBT values
+
+use std::{error::Error, fmt, io};
+
+/// errors from every fallible path in this crate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DbtError {
+  /// a character that is not one of `1`-`9`, `position` counts characters from the start of input
+  InvalidDigit { ch: char, position: usize },
+  /// a character that is not one of `1`-`9`, when reading a single digit without a string around it
+  InvalidDigitChar(char),
+  /// a number that is out of `1`-`9` when reading a digit from a number or a buffer
+  InvalidDigitValue(u8),
+  /// a pair of coordinates that does not point at one of the 9 digits
+  InvalidCoordinate { x: i64, y: i64 },
+  /// string representation does not start with `&`
+  MissingPrefix,
+  /// nothing after `&`, use `&5` for zero
+  EmptyNumber,
+  /// more than one `.` in string representation
+  TooManyRadixPoints,
+  /// buffer ends before the data its header announced
+  BufferTooShort,
+  /// integral part has more digits than a `FixedDbt` can hold
+  IntegralTooLong(usize),
+  /// `NaN` or infinity can not be represented
+  NonFiniteFloat,
+  /// a float with an integral part out of `i64`, which conversion goes through
+  FloatOutOfRange,
+  /// `&5` used as a divisor
+  DivisionByZero,
+  /// linear arithmetic got a value with digits other than `1`, `5` and `9`
+  NonLinearValue,
+  /// repeating digits are not closed by `)`, or there are no digits inside
+  UnclosedPeriod,
+  /// repeating digits were not found within given number of fractional digits
+  PeriodTooLong(usize),
+  /// integer arithmetic got a value with digits in fractional part
+  NonIntegralValue,
+  /// coordinates of the value do not fit in the integer type
+  IntegerOverflow,
+  /// `&5` has no factorization
+  ZeroValue,
+  /// trial division reached `TRIAL_DIVISION_LIMIT` with a factor of the norm still undecided
+  TrialDivisionLimit,
+  /// buffer does not start with the header of the expected format
+  UnknownFormat,
+  /// LEB128 length starting at this offset does not fit in `usize`
+  InvalidVarint(usize),
+  /// a `u16` group in packed format that is not smaller than `9^5`
+  InvalidPackedGroup(u16),
+  /// strict decoding found a byte at this offset that the encoder would not write
+  NonCanonical { offset: usize },
+  /// no single digit after the last `-`
+  MissingCheckDigit,
+  /// check digit does not match the digits before it
+  CheckDigitMismatch,
+}
+
+impl fmt::Display for DbtError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      DbtError::InvalidDigit { ch, position } => write!(f, "{} is not valid ternary digit representation, at {}", ch, position),
+      DbtError::InvalidDigitChar(ch) => write!(f, "{} is not valid ternary digit representation", ch),
+      DbtError::InvalidDigitValue(x) => write!(f, "unknown digit for dbt: {}", x),
+      DbtError::InvalidCoordinate { x, y } => write!(f, "unexpected coordinate for a digit: ({}, {})", x, y),
+      DbtError::MissingPrefix => write!(f, "ternary requires & symbol"),
+      DbtError::EmptyNumber => write!(f, "ternary requires a number, at least &5"),
+      DbtError::TooManyRadixPoints => write!(f, "invalid format for a ternary value, too many `.`"),
+      DbtError::BufferTooShort => write!(f, "dbt buffer length smaller than expected"),
+      DbtError::IntegralTooLong(n) => write!(f, "integral part too long: {}", n),
+      DbtError::NonFiniteFloat => write!(f, "non-finite float can not be converted to dbt"),
+      DbtError::FloatOutOfRange => write!(f, "float out of range for dbt conversion"),
+      DbtError::DivisionByZero => write!(f, "&5 is not a valid divisor"),
+      DbtError::NonLinearValue => write!(f, "only linear ternary values allowed"),
+      DbtError::UnclosedPeriod => write!(f, "repeating digits expected inside `(` and `)` at the end"),
+      DbtError::PeriodTooLong(n) => write!(f, "repeating digits not found within {} digits", n),
+      DbtError::NonIntegralValue => write!(f, "only integral ternary values allowed"),
+      DbtError::IntegerOverflow => write!(f, "ternary value out of integer range"),
+      DbtError::ZeroValue => write!(f, "&5 can not be factorized"),
+      DbtError::TrialDivisionLimit => write!(f, "norm too large for trial division"),
+      DbtError::UnknownFormat => write!(f, "unknown header for dbt buffer"),
+      DbtError::InvalidVarint(offset) => write!(f, "varint too large, at {}", offset),
+      DbtError::InvalidPackedGroup(n) => write!(f, "packed group out of range: {}", n),
+      DbtError::NonCanonical { offset } => write!(f, "non-canonical dbt buffer, at {}", offset),
+      DbtError::MissingCheckDigit => write!(f, "check digit expected after `-`"),
+      DbtError::CheckDigitMismatch => write!(f, "check digit does not match"),
+    }
+  }
+}
+
+impl Error for DbtError {}
+
+/// errors from decoding in streams are reported as `InvalidData`
+impl From<DbtError> for io::Error {
+  fn from(e: DbtError) -> Self {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+  }
+}
@@ -0,0 +1,274 @@
+//! Fixed width DBT values, stored inline without allocation.
+//!
+//! Overflow is handled per direction: wrapping drops the digits from `INT` on, which is `mod 3^INT`
+//! on both `1` and `3` directions, saturating pins the overflowed direction to its largest value.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::complex::ComplexXy;
+use crate::digit::{DualBalancedTernaryDigit, DualBalancedTernaryDigit::*};
+use crate::error::DbtError;
+use crate::primes::DualBalancedTernary;
+
+/// DBT value with `INT` integral digits and `FRAC` fractional digits, `Copy` and stored inline.
+/// digits are laid out like `DualBalancedTernary`, both parts start from the radix point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedDbt<const INT: usize, const FRAC: usize> {
+  integral: [DualBalancedTernaryDigit; INT],
+  fractional: [DualBalancedTernaryDigit; FRAC],
+}
+
+impl<const INT: usize, const FRAC: usize> FixedDbt<INT, FRAC> {
+  pub const ZERO: Self = FixedDbt {
+    integral: [Dbt5; INT],
+    fractional: [Dbt5; FRAC],
+  };
+
+  pub fn is_zero(&self) -> bool {
+    self.pairs().all(|(_, d)| d == Dbt5)
+  }
+
+  /// digits with positions, `0` for unit position, `-1` for first fractional position
+  fn pairs(&self) -> impl Iterator<Item = (i64, DualBalancedTernaryDigit)> + '_ {
+    let integral = self.integral.iter().enumerate().map(|(idx, d)| (idx as i64, *d));
+    let fractional = self.fractional.iter().enumerate().map(|(idx, d)| (-1 - idx as i64, *d));
+    integral.chain(fractional)
+  }
+
+  fn wide_add(&self, other: &Self) -> Wide<INT, FRAC> {
+    let mut result = Wide::from(*self);
+    for (idx, d) in other.pairs() {
+      result.add_at(idx, d);
+    }
+    result
+  }
+
+  /// digits lower than `FRAC` are cut off, which rounds to nearest on each direction
+  fn wide_mul(&self, other: &Self) -> Wide<INT, FRAC> {
+    let mut result = Wide::from(Self::ZERO);
+    for (a_idx, a_item) in self.pairs() {
+      for (b_idx, b_item) in other.pairs() {
+        let (carry, unit) = a_item * b_item;
+        result.add_at(a_idx + b_idx, unit);
+        result.add_at(a_idx + b_idx + 1, carry);
+      }
+    }
+    result
+  }
+
+  /// `None` when the result does not fit in `INT` integral digits
+  pub fn checked_add(&self, other: &Self) -> Option<Self> {
+    self.wide_add(other).checked()
+  }
+
+  pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+    self.checked_add(&-*other)
+  }
+
+  pub fn checked_mul(&self, other: &Self) -> Option<Self> {
+    self.wide_mul(other).checked()
+  }
+
+  /// wraps around `3^INT` on each direction, like a torus
+  pub fn wrapping_add(&self, other: &Self) -> Self {
+    self.wide_add(other).value
+  }
+
+  pub fn wrapping_sub(&self, other: &Self) -> Self {
+    self.wrapping_add(&-*other)
+  }
+
+  pub fn wrapping_mul(&self, other: &Self) -> Self {
+    self.wide_mul(other).value
+  }
+
+  /// an overflowed direction is pinned to its largest value in that sign, the other direction is kept
+  pub fn saturating_add(&self, other: &Self) -> Self {
+    self.wide_add(other).saturating()
+  }
+
+  pub fn saturating_sub(&self, other: &Self) -> Self {
+    self.saturating_add(&-*other)
+  }
+
+  pub fn saturating_mul(&self, other: &Self) -> Self {
+    self.wide_mul(other).saturating()
+  }
+}
+
+/// result of an operation before fitting into `FixedDbt`,
+/// `lo` holds digits below `FRAC`, `hi` holds digits from `INT` on, `top` takes anything higher
+struct Wide<const INT: usize, const FRAC: usize> {
+  lo: [DualBalancedTernaryDigit; FRAC],
+  value: FixedDbt<INT, FRAC>,
+  hi: [DualBalancedTernaryDigit; INT],
+  top: DualBalancedTernaryDigit,
+}
+
+impl<const INT: usize, const FRAC: usize> From<FixedDbt<INT, FRAC>> for Wide<INT, FRAC> {
+  fn from(value: FixedDbt<INT, FRAC>) -> Self {
+    Wide {
+      lo: [Dbt5; FRAC],
+      value,
+      hi: [Dbt5; INT],
+      top: Dbt5,
+    }
+  }
+}
+
+impl<const INT: usize, const FRAC: usize> Wide<INT, FRAC> {
+  fn slot(&mut self, idx: i64) -> Option<&mut DualBalancedTernaryDigit> {
+    let (int, frac) = (INT as i64, FRAC as i64);
+    if idx < -2 * frac {
+      None
+    } else if idx < -frac {
+      Some(&mut self.lo[(-1 - frac - idx) as usize])
+    } else if idx < 0 {
+      Some(&mut self.value.fractional[(-1 - idx) as usize])
+    } else if idx < int {
+      Some(&mut self.value.integral[idx as usize])
+    } else if idx < 2 * int {
+      Some(&mut self.hi[(idx - int) as usize])
+    } else {
+      Some(&mut self.top)
+    }
+  }
+
+  fn add_at(&mut self, idx: i64, d: DualBalancedTernaryDigit) {
+    let mut idx = idx;
+    let mut d = d;
+    while d != Dbt5 {
+      let is_top = idx >= 2 * INT as i64;
+      match self.slot(idx) {
+        Some(slot) => {
+          let (carry, unit) = *slot + d;
+          *slot = unit;
+          d = carry;
+        }
+        None => return,
+      }
+      if is_top {
+        return;
+      }
+      idx += 1;
+    }
+  }
+
+  /// signs of the overflowed parts on `3` and `1` directions, `0` for no overflow
+  fn overflow_signs(&self) -> (i64, i64) {
+    let mut signs = (0, 0);
+    for d in self.hi.iter().chain(iter::once(&self.top)) {
+      let c: ComplexXy = (*d).into();
+      if c.x != 0.0 {
+        signs.0 = c.x as i64;
+      }
+      if c.y != 0.0 {
+        signs.1 = c.y as i64;
+      }
+    }
+    signs
+  }
+
+  fn checked(&self) -> Option<FixedDbt<INT, FRAC>> {
+    match self.overflow_signs() {
+      (0, 0) => Some(self.value),
+      _ => None,
+    }
+  }
+
+  fn saturating(&self) -> FixedDbt<INT, FRAC> {
+    let (x_sign, y_sign) = self.overflow_signs();
+    let pin = |d: &mut DualBalancedTernaryDigit| {
+      let c: ComplexXy = (*d).into();
+      let x = if x_sign != 0 { x_sign } else { c.x as i64 };
+      let y = if y_sign != 0 { y_sign } else { c.y as i64 };
+      match DualBalancedTernaryDigit::try_from((x, y)) {
+        Ok(v) => *d = v,
+        Err(e) => unreachable!("signs always form a digit: {}", e),
+      }
+    };
+    let mut result = self.value;
+    result.integral.iter_mut().for_each(pin);
+    result.fractional.iter_mut().for_each(pin);
+    result
+  }
+}
+
+impl<const INT: usize, const FRAC: usize> Default for FixedDbt<INT, FRAC> {
+  fn default() -> Self {
+    Self::ZERO
+  }
+}
+
+impl<const INT: usize, const FRAC: usize> From<FixedDbt<INT, FRAC>> for DualBalancedTernary {
+  fn from(value: FixedDbt<INT, FRAC>) -> Self {
+    DualBalancedTernary::from_digits(value.integral.to_vec(), value.fractional.to_vec())
+  }
+}
+
+/// fractional digits beyond `FRAC` are cut off, which rounds to nearest on each direction,
+/// fails when integral part needs more than `INT` digits
+impl<const INT: usize, const FRAC: usize> TryFrom<DualBalancedTernary> for FixedDbt<INT, FRAC> {
+  type Error = DbtError;
+  fn try_from(value: DualBalancedTernary) -> Result<Self, Self::Error> {
+    if value.integral.len() > INT {
+      return Err(DbtError::IntegralTooLong(value.integral.len()));
+    }
+    let mut result = Self::ZERO;
+    result.integral[..value.integral.len()].copy_from_slice(&value.integral);
+    for (slot, d) in result.fractional.iter_mut().zip(value.fractional.iter()) {
+      *slot = *d;
+    }
+    Ok(result)
+  }
+}
+
+/// uses `&1.2` to write, like `DualBalancedTernary`
+impl<const INT: usize, const FRAC: usize> fmt::Display for FixedDbt<INT, FRAC> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", DualBalancedTernary::from(*self))
+  }
+}
+
+impl<const INT: usize, const FRAC: usize> Neg for FixedDbt<INT, FRAC> {
+  type Output = Self;
+  fn neg(self) -> Self {
+    let mut result = self;
+    result.integral.iter_mut().for_each(|d| *d = -*d);
+    result.fractional.iter_mut().for_each(|d| *d = -*d);
+    result
+  }
+}
+
+/// panics on overflow, see `checked_add`, `wrapping_add` and `saturating_add`
+impl<const INT: usize, const FRAC: usize> Add for FixedDbt<INT, FRAC> {
+  type Output = Self;
+  fn add(self, other: Self) -> Self {
+    match self.checked_add(&other) {
+      Some(v) => v,
+      None => panic!("fixed dbt overflow in addition"),
+    }
+  }
+}
+
+/// panics on overflow, see `checked_sub`, `wrapping_sub` and `saturating_sub`
+impl<const INT: usize, const FRAC: usize> Sub for FixedDbt<INT, FRAC> {
+  type Output = Self;
+  fn sub(self, other: Self) -> Self {
+    self.add(-other)
+  }
+}
+
+/// panics on overflow, see `checked_mul`, `wrapping_mul` and `saturating_mul`
+impl<const INT: usize, const FRAC: usize> Mul for FixedDbt<INT, FRAC> {
+  type Output = Self;
+  fn mul(self, other: Self) -> Self {
+    match self.checked_mul(&other) {
+      Some(v) => v,
+      None => panic!("fixed dbt overflow in multiplication"),
+    }
+  }
+}
@@ -0,0 +1,124 @@
+//! Euclidean arithmetic on DBT integers.
+//!
+//! DBT integers are Gaussian integers in another notation, `&1` is the identity and `&3` squares to `&9`,
+//! so rounding a quotient to the nearest lattice point gives a remainder with a smaller norm.
+
+use std::ops::Rem;
+
+use crate::context::ArithmeticContext;
+use crate::digit::DualBalancedTernaryDigit::*;
+use crate::error::DbtError;
+use crate::primes::{DualBalancedTernary, ONE, ZERO};
+
+impl DualBalancedTernary {
+  /// `true` when there is no digit in fractional part
+  pub fn is_integral(&self) -> bool {
+    self.fractional.iter().all(|d| *d == Dbt5)
+  }
+
+  /// signs of the value on `3` direction and `1` direction
+  pub(crate) fn signs_xy(&self) -> (i64, i64) {
+    let (x, y) = self.split_yx();
+    (x.rotate7().linear_sign(), y.linear_sign())
+  }
+
+  /// quotient rounded to the nearest DBT integer on each axis,
+  /// balanced digits make it a plain cut at position 0
+  pub(crate) fn nearest_div(&self, other: &DualBalancedTernary) -> Result<DualBalancedTernary, DbtError> {
+    self.div_with(other, &ArithmeticContext::with_precision(0))
+  }
+
+  /// the associate in `1` direction quadrant, i.e. `1` side positive and `3` side not negative,
+  /// returned with the unit it was multiplied by
+  pub(crate) fn associate_parts(&self) -> (DualBalancedTernary, DualBalancedTernary) {
+    let one = ONE.clone();
+    match self.signs_xy() {
+      (0, 0) => (self.to_owned(), one),
+      (x, y) if y > 0 && x >= 0 => (self.to_owned(), one),
+      (x, y) if x > 0 && y <= 0 => (self.rotate7(), one.rotate7()),
+      (x, y) if x <= 0 && y < 0 => (-self.to_owned(), -one),
+      _ => (self.rotate3(), one.rotate3()),
+    }
+  }
+
+  /// Euclidean division, the quotient is rounded to the nearest DBT integer on each axis,
+  /// so the remainder `self - q * other` has a norm smaller than `other`, at most half of it.
+  /// made for integral values, while fractional values also get an integral quotient.
+  /// panics when `other` is `&5`
+  pub fn div_rem(&self, other: &DualBalancedTernary) -> (DualBalancedTernary, DualBalancedTernary) {
+    match self.try_div_rem(other) {
+      Ok(v) => v,
+      Err(e) => panic!("{}", e),
+    }
+  }
+
+  /// like `div_rem`, but returns an error for `&5` divisors
+  pub fn try_div_rem(&self, other: &DualBalancedTernary) -> Result<(DualBalancedTernary, DualBalancedTernary), DbtError> {
+    let q = self.nearest_div(other)?;
+    let r = self - &q * other;
+    Ok((q, r))
+  }
+
+  /// like `div_rem`, but returns `None` for `&5` divisors
+  pub fn checked_div_rem(&self, other: &DualBalancedTernary) -> Option<(DualBalancedTernary, DualBalancedTernary)> {
+    self.try_div_rem(other).ok()
+  }
+
+  /// the associate with positive `1` side and not negative `3` side,
+  /// i.e. `self` multiplied by one of `&1`, `&3`, `&9`, `&7`, so associates share one representative
+  pub fn normalize_associate(&self) -> DualBalancedTernary {
+    self.associate_parts().0
+  }
+
+  /// greatest common divisor with Euclid's algorithm, in the form of `normalize_associate`.
+  /// made for integral values, `&5` when both are `&5`
+  pub fn gcd(&self, other: &DualBalancedTernary) -> DualBalancedTernary {
+    let mut a = self.to_owned();
+    let mut b = other.to_owned();
+    while !b.is_zero() {
+      let (_, r) = a.div_rem(&b);
+      a = b;
+      b = r;
+    }
+    a.normalize_associate()
+  }
+
+  /// least common multiple, in the form of `normalize_associate`, `&5` when either is `&5`
+  pub fn lcm(&self, other: &DualBalancedTernary) -> DualBalancedTernary {
+    if self.is_zero() || other.is_zero() {
+      return ZERO;
+    }
+    let g = self.gcd(other);
+    // division by GCD is exact
+    let (q, _) = self.div_rem(&g);
+    (q * other).normalize_associate()
+  }
+
+  /// `(g, s, t)` with `self * s + other * t = g`, where `g` is the `gcd`
+  pub fn extended_gcd(&self, other: &DualBalancedTernary) -> (DualBalancedTernary, DualBalancedTernary, DualBalancedTernary) {
+    let (mut a, mut s0, mut t0) = (self.to_owned(), ONE.clone(), ZERO);
+    let (mut b, mut s1, mut t1) = (other.to_owned(), ZERO, ONE.clone());
+    while !b.is_zero() {
+      let (q, r) = a.div_rem(&b);
+      let s2 = s0 - &q * &s1;
+      let t2 = t0 - q * &t1;
+      a = b;
+      b = r;
+      s0 = s1;
+      s1 = s2;
+      t0 = t1;
+      t1 = t2;
+    }
+    let (g, unit) = a.associate_parts();
+    (g, s0 * &unit, t0 * unit)
+  }
+}
+
+/// remainder of `div_rem`, panics when divisor is `&5`
+impl Rem for DualBalancedTernary {
+  type Output = Self;
+
+  fn rem(self, other: DualBalancedTernary) -> Self {
+    self.div_rem(&other).1
+  }
+}
@@ -31,18 +31,48 @@
 //!
 //! The math is roughly equal to Complex numbers, expect for that its identity value is `1` pointing at at front.
 
+#[cfg(feature = "num-bigint")]
+mod bigint;
+mod check;
+pub mod codec;
+mod compact;
 pub mod complex;
+pub mod context;
+mod convert;
 pub mod digit;
+pub mod error;
+pub mod fixed;
+mod integer;
+mod multiply;
+#[cfg(feature = "num-traits")]
+mod numeric;
+mod order;
 pub mod primes;
+pub mod rational;
+pub mod repeating;
+#[cfg(feature = "serde")]
+pub mod serde_as;
+pub mod stream;
 
+pub use context::{ArithmeticContext, RoundingMode};
 pub use digit::DualBalancedTernaryDigit;
-pub use primes::{DualBalancedTernary, DIV_PRECISION};
+pub use error::DbtError;
+pub use fixed::FixedDbt;
+pub use primes::{DualBalancedTernary, Factorization, Quotient, DIV_PRECISION, ONE, TRIAL_DIVISION_LIMIT, ZERO};
+pub use rational::DbtRational;
+pub use repeating::{RepeatingTernary, MAX_REPEATING_DIGITS};
+pub use stream::{DbtReader, DbtWriter};
 
 use std::str::FromStr;
 
 /// an alias for quick creating a DualBalancedTernary, might fail
 pub fn ternary(s: &str) -> DualBalancedTernary {
-  DualBalancedTernary::from_str(s).unwrap()
+  try_ternary(s).unwrap()
+}
+
+/// like `ternary`, but returns the error instead of panicking
+pub fn try_ternary(s: &str) -> Result<DualBalancedTernary, DbtError> {
+  DualBalancedTernary::from_str(s)
 }
 
 /// expose internal digits for inspecting
@@ -0,0 +1,136 @@
+//! Multiplication on digit coordinates.
+//!
+//! A value is a polynomial in `3` whose coefficients are the `(x, y)` of its digits, Gaussian integers.
+//! Coefficients of the product are collected first and carried on each direction at the end,
+//! long operands are split with Karatsuba, so digits are multiplied in `O(n^1.585)`.
+
+use std::convert::TryFrom;
+
+use crate::complex::ComplexXy;
+use crate::digit::{DualBalancedTernaryDigit, DualBalancedTernaryDigit::*};
+use crate::primes::DualBalancedTernary;
+
+/// `(x, y)` of a coefficient, which is `y + x * i`
+type Coef = (i64, i64);
+
+/// Karatsuba splits operands when both are at least this long, shorter ones are multiplied digit by digit
+const KARATSUBA_THRESHOLD: usize = 32;
+
+fn coef_mul(a: Coef, b: Coef) -> Coef {
+  (a.1 * b.0 + a.0 * b.1, a.1 * b.1 - a.0 * b.0)
+}
+
+/// adds `src` into `dst` from `offset`, growing `dst` when needed
+fn add_into(dst: &mut Vec<Coef>, offset: usize, src: &[Coef]) {
+  if dst.len() < offset + src.len() {
+    dst.resize(offset + src.len(), (0, 0));
+  }
+  for (idx, c) in src.iter().enumerate() {
+    dst[offset + idx].0 += c.0;
+    dst[offset + idx].1 += c.1;
+  }
+}
+
+fn sub_from(dst: &mut [Coef], src: &[Coef]) {
+  for (idx, c) in src.iter().enumerate() {
+    dst[idx].0 -= c.0;
+    dst[idx].1 -= c.1;
+  }
+}
+
+fn schoolbook(a: &[Coef], b: &[Coef]) -> Vec<Coef> {
+  let mut result = vec![(0, 0); a.len() + b.len() - 1];
+  for (i, p) in a.iter().enumerate() {
+    if *p == (0, 0) {
+      continue;
+    }
+    for (j, q) in b.iter().enumerate() {
+      let c = coef_mul(*p, *q);
+      result[i + j].0 += c.0;
+      result[i + j].1 += c.1;
+    }
+  }
+  result
+}
+
+fn karatsuba(a: &[Coef], b: &[Coef]) -> Vec<Coef> {
+  if a.is_empty() || b.is_empty() {
+    return vec![];
+  }
+  let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+  if b.len() < KARATSUBA_THRESHOLD {
+    return schoolbook(a, b);
+  }
+  let mut result = vec![];
+  if b.len() * 2 <= a.len() {
+    // unbalanced operands, multiplied in slices as long as the shorter one
+    for (idx, chunk) in a.chunks(b.len()).enumerate() {
+      add_into(&mut result, idx * b.len(), &karatsuba(chunk, b));
+    }
+    return result;
+  }
+  let m = a.len() / 2;
+  let (a0, a1) = a.split_at(m);
+  let (b0, b1) = b.split_at(m);
+  let z0 = karatsuba(a0, b0);
+  let z2 = karatsuba(a1, b1);
+  let mut sum_a = a1.to_vec();
+  add_into(&mut sum_a, 0, a0);
+  let mut sum_b = b1.to_vec();
+  add_into(&mut sum_b, 0, b0);
+  let mut z1 = karatsuba(&sum_a, &sum_b);
+  sub_from(&mut z1, &z0);
+  sub_from(&mut z1, &z2);
+  add_into(&mut result, 0, &z0);
+  add_into(&mut result, m, &z1);
+  add_into(&mut result, 2 * m, &z2);
+  result
+}
+
+fn coef(d: DualBalancedTernaryDigit) -> Coef {
+  let c = ComplexXy::from(d);
+  (c.x as i64, c.y as i64)
+}
+
+/// coefficients from the last fractional digit
+fn coefs(value: &DualBalancedTernary) -> Vec<Coef> {
+  value.fractional.iter().rev().chain(&value.integral).map(|d| coef(*d)).collect()
+}
+
+/// splits `v` into a balanced trit and a carry
+fn balanced(v: i64) -> (i64, i64) {
+  let t = match v.rem_euclid(3) {
+    2 => -1,
+    t => t,
+  };
+  (t, (v - t) / 3)
+}
+
+impl DualBalancedTernary {
+  /// product of digits, used by `*`
+  pub(crate) fn mul_digits(&self, other: &DualBalancedTernary) -> DualBalancedTernary {
+    let product = karatsuba(&coefs(self), &coefs(other));
+    let shift = self.fractional.len() + other.fractional.len();
+    let mut digits = Vec::with_capacity(product.len().max(shift) + 2);
+    let mut carry: Coef = (0, 0);
+    let mut idx = 0;
+    // carried on both directions together, into digits from the lowest position
+    while idx < product.len() || carry != (0, 0) {
+      let c = product.get(idx).unwrap_or(&(0, 0));
+      let (x, cx) = balanced(c.0 + carry.0);
+      let (y, cy) = balanced(c.1 + carry.1);
+      match DualBalancedTernaryDigit::try_from((x, y)) {
+        Ok(d) => digits.push(d),
+        Err(e) => unreachable!("balanced trits always form a digit: {}", e),
+      }
+      carry = (cx, cy);
+      idx += 1;
+    }
+    if digits.len() < shift {
+      digits.resize(shift, Dbt5);
+    }
+    let integral = digits.split_off(shift);
+    digits.reverse();
+    DualBalancedTernary::from_digits(integral, digits)
+  }
+}
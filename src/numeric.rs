@@ -0,0 +1,140 @@
+//! `num-traits` integration, behind `num-traits` feature.
+//!
+//! Integers are placed on `1` direction like `From<i64>`, and conversions back to primitives
+//! give `None` for values with digits on `3` direction or, for integer types, in fractional part.
+
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use num_traits::{FromPrimitive, Inv, Num, One, Pow, ToPrimitive, Zero};
+
+use crate::complex::ComplexXy;
+use crate::error::DbtError;
+use crate::primes::{DualBalancedTernary, ONE, ZERO};
+
+impl Zero for DualBalancedTernary {
+  fn zero() -> Self {
+    ZERO
+  }
+
+  fn is_zero(&self) -> bool {
+    DualBalancedTernary::is_zero(self)
+  }
+}
+
+impl One for DualBalancedTernary {
+  fn one() -> Self {
+    ONE.clone()
+  }
+}
+
+impl Num for DualBalancedTernary {
+  type FromStrRadixErr = DbtError;
+
+  /// `&1.1` form is read for any radix, other strings are read as integers in `radix`
+  fn from_str_radix(s: &str, radix: u32) -> Result<Self, DbtError> {
+    if s.starts_with('&') {
+      return DualBalancedTernary::from_str(s);
+    }
+    match i128::from_str_radix(s, radix) {
+      Ok(n) => Ok(DualBalancedTernary::from(n)),
+      Err(_) => {
+        let digits = s.strip_prefix(['+', '-']).unwrap_or(s);
+        let sign_len = s.len() - digits.len();
+        match digits.chars().position(|c| !c.is_digit(radix)) {
+          _ if digits.is_empty() => Err(DbtError::EmptyNumber),
+          Some(idx) => Err(DbtError::InvalidDigit {
+            ch: digits.chars().nth(idx).unwrap_or('?'),
+            position: sign_len + idx,
+          }),
+          None => Err(DbtError::IntegerOverflow),
+        }
+      }
+    }
+  }
+}
+
+impl FromPrimitive for DualBalancedTernary {
+  fn from_i64(n: i64) -> Option<Self> {
+    Some(DualBalancedTernary::from(n))
+  }
+
+  fn from_u64(n: u64) -> Option<Self> {
+    Some(DualBalancedTernary::from(n))
+  }
+
+  fn from_i128(n: i128) -> Option<Self> {
+    Some(DualBalancedTernary::from(n))
+  }
+
+  fn from_u128(n: u128) -> Option<Self> {
+    Some(DualBalancedTernary::from(n))
+  }
+
+  /// on `1` direction like integers, digits are limited by `DIV_PRECISION`
+  fn from_f64(n: f64) -> Option<Self> {
+    DualBalancedTernary::try_from((0.0, n)).ok()
+  }
+}
+
+impl ToPrimitive for DualBalancedTernary {
+  fn to_i64(&self) -> Option<i64> {
+    i64::try_from(self.to_owned()).ok()
+  }
+
+  fn to_u64(&self) -> Option<u64> {
+    u64::try_from(self.to_owned()).ok()
+  }
+
+  fn to_i128(&self) -> Option<i128> {
+    i128::try_from(self.to_owned()).ok()
+  }
+
+  fn to_u128(&self) -> Option<u128> {
+    u128::try_from(self.to_owned()).ok()
+  }
+
+  fn to_f64(&self) -> Option<f64> {
+    let c = ComplexXy::from(self.to_owned());
+    if c.x == 0.0 {
+      Some(c.y)
+    } else {
+      None
+    }
+  }
+}
+
+/// `&1 / self`, with the same digits as `/`, panics for `&5`
+impl Inv for DualBalancedTernary {
+  type Output = Self;
+  fn inv(self) -> Self {
+    &*ONE / self
+  }
+}
+
+/// exponentiation by squaring
+impl Pow<usize> for DualBalancedTernary {
+  type Output = Self;
+  fn pow(self, exp: usize) -> Self {
+    let mut result = DualBalancedTernary::one();
+    let mut base = self;
+    let mut exp = exp;
+    while exp > 0 {
+      if exp & 1 == 1 {
+        result *= &base;
+      }
+      exp >>= 1;
+      if exp > 0 {
+        base = &base * &base;
+      }
+    }
+    result
+  }
+}
+
+impl Pow<u32> for DualBalancedTernary {
+  type Output = Self;
+  fn pow(self, exp: u32) -> Self {
+    self.pow(exp as usize)
+  }
+}
@@ -0,0 +1,102 @@
+//! Ordering of DBT values.
+//!
+//! `Ord` compares exact values on `1` direction first, then on `3` direction, so values on `1` direction
+//! are ordered like real numbers and `BTreeMap` keys come out in that order.
+//! The `cmp_*` methods are comparators for `sort_by`, `cmp_norm` and `cmp_angle` give `Equal` for different values.
+
+use std::cmp::Ordering;
+
+use crate::complex::ComplexXy;
+use crate::digit::{DualBalancedTernaryDigit, DualBalancedTernaryDigit::*};
+use crate::primes::{DualBalancedTernary, ONE, ZERO};
+
+/// picks the trit of one direction from a digit
+type Axis = fn(DualBalancedTernaryDigit) -> i64;
+
+fn axis_x(d: DualBalancedTernaryDigit) -> i64 {
+  ComplexXy::from(d).x as i64
+}
+
+fn axis_y(d: DualBalancedTernaryDigit) -> i64 {
+  ComplexXy::from(d).y as i64
+}
+
+impl DualBalancedTernary {
+  fn digit_at(&self, idx: i64) -> DualBalancedTernaryDigit {
+    let found = if idx >= 0 {
+      self.integral.get(idx as usize)
+    } else {
+      self.fractional.get((-1 - idx) as usize)
+    };
+    *found.unwrap_or(&Dbt5)
+  }
+
+  /// balanced trits of one direction, compared from the highest position,
+  /// since lower positions add up to less than one unit of a higher position
+  fn cmp_axis(&self, other: &DualBalancedTernary, axis: Axis) -> Ordering {
+    let top = self.integral.len().max(other.integral.len()) as i64;
+    let bottom = -(self.fractional.len().max(other.fractional.len()) as i64);
+    for idx in (bottom..top).rev() {
+      match axis(self.digit_at(idx)).cmp(&axis(other.digit_at(idx))) {
+        Ordering::Equal => continue,
+        ord => return ord,
+      }
+    }
+    Ordering::Equal
+  }
+
+  /// sign of one direction, as an `Ordering` against zero
+  fn sign_axis(&self, axis: Axis) -> Ordering {
+    self.cmp_axis(&ZERO, axis)
+  }
+
+  /// compares exact values on `1` direction, the real part
+  pub fn cmp_y(&self, other: &DualBalancedTernary) -> Ordering {
+    self.cmp_axis(other, axis_y)
+  }
+
+  /// compares exact values on `3` direction, the imaginary part
+  pub fn cmp_x(&self, other: &DualBalancedTernary) -> Ordering {
+    self.cmp_axis(other, axis_x)
+  }
+
+  /// compares exact norms, `x * x + y * y`, computed as `self * self.conjugate()`
+  pub fn cmp_norm(&self, other: &DualBalancedTernary) -> Ordering {
+    let a = self * &self.conjugate();
+    let b = other * &other.conjugate();
+    a.cmp_y(&b)
+  }
+
+  /// compares exact angles in `(-π, π]`, like `atan2(x, y)`, and `&5` counts as angle `0`.
+  /// within a half plane, the sign of `x` in `conjugate(self) * other` tells which one turns further
+  pub fn cmp_angle(&self, other: &DualBalancedTernary) -> Ordering {
+    // `&5` has no direction, it goes with `&1`
+    let (a, b) = (
+      if self.is_zero() { &*ONE } else { self },
+      if other.is_zero() { &*ONE } else { other },
+    );
+    let upper = |v: &DualBalancedTernary| match v.sign_axis(axis_x) {
+      Ordering::Greater => true,
+      Ordering::Less => false,
+      Ordering::Equal => v.sign_axis(axis_y) == Ordering::Less,
+    };
+    match (upper(a), upper(b)) {
+      (false, true) => Ordering::Less,
+      (true, false) => Ordering::Greater,
+      _ => (&a.conjugate() * b).sign_axis(axis_x).reverse(),
+    }
+  }
+}
+
+/// exact order on `1` direction, then on `3` direction
+impl Ord for DualBalancedTernary {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.cmp_y(other).then_with(|| self.cmp_x(other))
+  }
+}
+
+impl PartialOrd for DualBalancedTernary {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
@@ -1,27 +1,59 @@
+use std::cmp::Ordering;
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
 use std::hash::{Hash, Hasher};
-use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 use std::str::FromStr;
+use std::sync::LazyLock;
 
+use crate::codec;
 use crate::complex::ComplexXy;
 use crate::digit::{DualBalancedTernaryDigit, DualBalancedTernaryDigit::*};
+use crate::error::DbtError;
 
-/// how many digits in fractional part, when it's not divisible
+/// how many digits in fractional part, when it's not divisible, used by operators,
+/// see `ArithmeticContext` for choosing precision per call
 pub const DIV_PRECISION: usize = 10;
 
-const ZERO: DualBalancedTernary = DualBalancedTernary {
+/// largest divisor tried by `is_prime` and `factorize`, norms with no factor up to it
+/// are decided only when they are below its square, or an error is returned
+pub const TRIAL_DIVISION_LIMIT: u128 = 1 << 24;
+
+/// `&5`, the value without digits
+pub const ZERO: DualBalancedTernary = DualBalancedTernary {
   integral: vec![],
   fractional: vec![],
 };
 
+/// `&1`, a `static` since a `const` can not hold a `Vec` with digits
+pub static ONE: LazyLock<DualBalancedTernary> = LazyLock::new(|| DualBalancedTernary {
+  integral: vec![Dbt1],
+  fractional: vec![],
+});
+
+/// result of a division, `is_exact` is `false` when digits were cut off after `DIV_PRECISION * 2` steps
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Quotient {
+  pub value: DualBalancedTernary,
+  pub is_exact: bool,
+}
+
 /// Dual Balanced Ternary represented in limited accuracy.
+/// Values are kept canonical, without `5`s at the far ends of both parts, see `from_digits`
 #[derive(Debug, Clone)]
 pub struct DualBalancedTernary {
   /// integral part, digits near 0 are placed first
-  pub integral: Vec<DualBalancedTernaryDigit>,
+  pub(crate) integral: Vec<DualBalancedTernaryDigit>,
   /// fractional part, digits near 0 are placed first
-  pub fractional: Vec<DualBalancedTernaryDigit>,
+  pub(crate) fractional: Vec<DualBalancedTernaryDigit>,
+}
+
+/// digits without `5`s at the far end
+fn trimmed(digits: &[DualBalancedTernaryDigit]) -> &[DualBalancedTernaryDigit] {
+  match digits.iter().rposition(|d| *d != Dbt5) {
+    Some(idx) => &digits[..=idx],
+    None => &[],
+  }
 }
 
 /// uses `&1.2` to write. notice `5` is the zero point
@@ -46,18 +78,36 @@ impl fmt::Display for DualBalancedTernary {
 }
 
 impl TryFrom<f64> for DualBalancedTernary {
-  type Error = String;
+  type Error = DbtError;
 
   fn try_from(x: f64) -> Result<Self, Self::Error> {
-    let mut result = DualBalancedTernary {
-      integral: vec![],
-      fractional: vec![],
-    };
+    Ok(Self::from_f64_digits(x, DIV_PRECISION)?.0)
+  }
+}
+
+impl DualBalancedTernary {
+  /// converts float into `3` direction with `precision` digits in fractional part,
+  /// also returns the part that was dropped, counted in units of the last digit
+  pub(crate) fn from_f64_digits(x: f64, precision: usize) -> Result<(Self, f64), DbtError> {
+    if !x.is_finite() {
+      return Err(DbtError::NonFiniteFloat);
+    }
+    let mut result = ZERO;
 
     let negative_value = x < 0.0;
 
-    let mut integral_part = x.floor() as i64;
-    let mut fractional_part = x - x.floor();
+    let mut floor = x.floor();
+    let mut fractional_part = x - floor;
+    // tiny negative values like `-1e-20` round `x - floor` up to `1.0`, carry it into the unit digit
+    if fractional_part >= 1.0 {
+      floor += 1.0;
+      fractional_part = 0.0;
+    }
+    // `as` would saturate, and `i64::MIN` can not be negated below
+    if floor.abs() >= i64::MAX as f64 {
+      return Err(DbtError::FloatOutOfRange);
+    }
+    let mut integral_part = floor as i64;
     if negative_value {
       integral_part = 0 - integral_part;
     }
@@ -69,10 +119,10 @@ impl TryFrom<f64> for DualBalancedTernary {
       if left == 0 {
         // nothing
       } else if left == 1 {
-        result = result.add_at(idx, Dbt3);
+        result.add_at_mut(idx, Dbt3);
       } else if left == 2 {
-        result = result.add_at(idx + 1, Dbt3);
-        result = result.add_at(idx, Dbt7);
+        result.add_at_mut(idx + 1, Dbt3);
+        result.add_at_mut(idx, Dbt7);
       } else {
         unreachable!("unexpected reminder: {} from {}", left, x)
       }
@@ -81,45 +131,41 @@ impl TryFrom<f64> for DualBalancedTernary {
     }
 
     if negative_value {
-      for (idx, item) in result.integral.to_owned().iter().enumerate() {
-        result.integral[idx] = -*item;
+      for item in result.integral.iter_mut() {
+        *item = -*item;
       }
     }
 
     let mut f_idx = -1;
-    let mut precision = DIV_PRECISION; // TODO
+    let mut precision = precision;
     while fractional_part > 0.0 && precision > 0 {
       fractional_part *= 3.0;
-      let left = fractional_part.floor();
-      if left == 0.0 {
+      // `fractional_part` stays below `1.0`, clamped in case rounding reaches `3.0`
+      let left = fractional_part.floor().min(2.0);
+      if left < 0.5 {
         // nothing
-      } else if (left - 1.0).abs() < f64::EPSILON {
-        result = result.add_at(f_idx, Dbt3);
-      } else if (left - 2.0).abs() < f64::EPSILON {
-        result = result.add_at(f_idx + 1, Dbt3);
-        result = result.add_at(f_idx, Dbt7);
+      } else if left < 1.5 {
+        result.add_at_mut(f_idx, Dbt3);
       } else {
-        return Err(format!("unexpected carry: {} from {}", left, fractional_part));
+        result.add_at_mut(f_idx + 1, Dbt3);
+        result.add_at_mut(f_idx, Dbt7);
       }
       fractional_part -= left;
       f_idx -= 1;
       precision -= 1;
     }
-    Ok(result)
+    Ok((result, fractional_part))
   }
 }
 
 impl TryFrom<(f64, f64)> for DualBalancedTernary {
-  type Error = String;
+  type Error = DbtError;
   fn try_from(pair: (f64, f64)) -> Result<Self, Self::Error> {
     let (x, y) = pair;
     let a: DualBalancedTernary = x.try_into()?;
     let mut b: DualBalancedTernary = y.try_into()?;
-    for (idx, item) in b.integral.to_owned().iter().enumerate() {
-      b.integral[idx] = item.flip_xy();
-    }
-    for (idx, item) in b.fractional.to_owned().iter().enumerate() {
-      b.fractional[idx] = item.flip_xy();
+    for item in b.integral.iter_mut().chain(b.fractional.iter_mut()) {
+      *item = item.flip_xy();
     }
     Ok(a + b)
   }
@@ -129,11 +175,8 @@ impl Neg for DualBalancedTernary {
   type Output = Self;
   fn neg(self) -> Self {
     let mut result: DualBalancedTernary = self;
-    for i in 0..result.integral.len() {
-      result.integral[i] = -result.integral[i];
-    }
-    for i in 0..result.fractional.len() {
-      result.fractional[i] = -result.fractional[i];
+    for item in result.integral.iter_mut().chain(result.fractional.iter_mut()) {
+      *item = -*item;
     }
     result
   }
@@ -161,102 +204,79 @@ impl From<DualBalancedTernary> for ComplexXy {
   }
 }
 
-impl TryFrom<DualBalancedTernary> for Vec<u8> {
-  type Error = String;
-
-  /// buffer format
-  /// [integral length]+[integral pairs]+[fractional pairs]
-  fn try_from(value: DualBalancedTernary) -> Result<Self, String> {
-    // make sure no extra `5`s is generated into buffer
-    let v = value.strip_empty_tails();
-    let int_len = v.integral.len();
-    if int_len < 256 {
-      let mut buf: Vec<u8> = vec![int_len as u8];
-      // for integral part, put space 5 at head
-      let mut halfed = false;
-      let mut prev: u8 = 0;
-      for x in &v.integral {
-        if halfed {
-          prev += u8::from(*x);
-          buf.push(prev.to_owned());
-          halfed = false;
-        } else {
-          prev = u8::from(*x) << 4;
-          halfed = true;
-        }
-      }
-      if halfed {
-        prev += 5;
-        buf.push(prev.to_owned());
-        halfed = false;
-      }
+/// like `try_new`, digits are limited by `DIV_PRECISION`
+impl TryFrom<ComplexXy> for DualBalancedTernary {
+  type Error = DbtError;
+  fn try_from(c: ComplexXy) -> Result<Self, Self::Error> {
+    Self::try_new(c.x, c.y)
+  }
+}
 
-      // expected handled by pair
-      assert_eq!(buf.len(), ((int_len + 1) >> 1) + 1);
-
-      // for integral part, put space 5 at tail
-      for x in &v.fractional {
-        if halfed {
-          prev += u8::from(*x);
-          buf.push(prev.to_owned());
-          halfed = false;
-        } else {
-          prev = u8::from(*x) << 4;
-          halfed = true;
-        }
-      }
-      if halfed {
-        prev += 5;
-        buf.push(prev.to_owned());
-      }
+impl TryFrom<DualBalancedTernary> for Vec<u8> {
+  type Error = DbtError;
 
-      Ok(buf)
-    } else {
-      Err(format!("integral part too long: {}", int_len))
-    }
+  /// buffer format, see `codec` for details
+  /// [0xDB 0x01]+[integral length]+[fractional length]+[digit pairs]
+  fn try_from(value: DualBalancedTernary) -> Result<Self, Self::Error> {
+    Ok(codec::encode_nibbles(&value))
   }
 }
 
 impl TryFrom<&Vec<u8>> for DualBalancedTernary {
-  type Error = String;
-  /// buffer format
+  type Error = DbtError;
+  /// reads versioned buffers, packed buffers, and legacy buffers in the format of
   /// [integral length]+[integral pairs]+[fractional pairs]
   fn try_from(buf: &Vec<u8>) -> Result<Self, Self::Error> {
-    if buf.is_empty() {
-      return Err(String::from("dbt buffer expected >=2 u8 numbers"));
-    }
+    codec::decode(buf)
+  }
+}
 
-    let int_range = (buf[0] + 1) as usize >> 1;
+/// like `TryFrom<&Vec<u8>>`, for borrowed slices
+impl TryFrom<&[u8]> for DualBalancedTernary {
+  type Error = DbtError;
+  fn try_from(buf: &[u8]) -> Result<Self, Self::Error> {
+    codec::decode(buf)
+  }
+}
 
-    if buf.len() < (int_range + 1) {
-      return Err(String::from("dbt buffer length smaller than integral size"));
-    }
-    let mut integral: Vec<DualBalancedTernaryDigit> = vec![];
-    let mut fractional: Vec<DualBalancedTernaryDigit> = vec![];
+impl DualBalancedTernary {
+  /// builds a value from digits near 0 first in both parts, `5`s at the far ends are dropped
+  pub fn from_digits(integral: Vec<DualBalancedTernaryDigit>, fractional: Vec<DualBalancedTernaryDigit>) -> Self {
+    let mut value = DualBalancedTernary { integral, fractional };
+    value.trim_mut();
+    value
+  }
 
-    // println!("buffer: {:?}", buf);
-    for (idx, x) in buf.iter().enumerate() {
-      if idx < 1 {
-        continue;
-      }
-      // println!("reading: {} {}", idx, x);
-      if idx < int_range + 1 {
-        integral.push(DualBalancedTernaryDigit::try_from((x & 0b11110000) >> 4)?);
-        integral.push(DualBalancedTernaryDigit::try_from(x & 0b00001111)?);
-      } else {
-        fractional.push(DualBalancedTernaryDigit::try_from((x & 0b11110000) >> 4)?);
-        fractional.push(DualBalancedTernaryDigit::try_from(x & 0b00001111)?);
-      }
-    }
+  /// integral digits, from the unit position up
+  pub fn integral(&self) -> &[DualBalancedTernaryDigit] {
+    &self.integral
+  }
 
-    Ok(Self { integral, fractional }.strip_empty_tails())
+  /// fractional digits, from the first position after the radix point down
+  pub fn fractional(&self) -> &[DualBalancedTernaryDigit] {
+    &self.fractional
   }
-}
 
-impl DualBalancedTernary {
-  /// created like a complex number, but notice DBT has main direction at `1`
+  /// integral and fractional digits, in the order of `integral()` and `fractional()`
+  pub fn into_digits(self) -> (Vec<DualBalancedTernaryDigit>, Vec<DualBalancedTernaryDigit>) {
+    (self.integral, self.fractional)
+  }
+
+  /// drops `5`s at the far ends, every method changing digits in place ends with this
+  pub(crate) fn trim_mut(&mut self) {
+    self.integral.truncate(trimmed(&self.integral).len());
+    self.fractional.truncate(trimmed(&self.fractional).len());
+  }
+
+  /// created like a complex number, but notice DBT has main direction at `1`,
+  /// use `From<(i64, i64)>` for exact integer coordinates
   pub fn new(x: f64, y: f64) -> Self {
-    (x, y).try_into().unwrap()
+    Self::try_new(x, y).unwrap()
+  }
+
+  /// like `new`, but returns an error for `NaN` and infinite values
+  pub fn try_new(x: f64, y: f64) -> Result<Self, DbtError> {
+    (x, y).try_into()
   }
 
   // TODO positive number to make value larger, not in use yet
@@ -269,7 +289,7 @@ impl DualBalancedTernary {
           if b.fractional.is_empty() {
             b.integral.insert(0, Dbt5);
           } else {
-            b.integral.insert(0, b.fractional.to_owned()[0]);
+            b.integral.insert(0, b.fractional[0]);
             b.fractional.remove(0);
           }
         }
@@ -286,69 +306,110 @@ impl DualBalancedTernary {
         }
       }
     };
+    b.trim_mut();
     b
   }
 
   // 0 for unit position, -1 for first fractional position
   pub fn add_at(&self, idx: i64, d: DualBalancedTernaryDigit) -> DualBalancedTernary {
-    // echo "to add: ", a, " ", d, " at: ", idx
-    if d == Dbt5 {
-      return self.to_owned();
-    }
     let mut b = self.to_owned();
-    if idx >= 0 {
-      if idx > self.integral.len() as i64 - 1 {
-        let mut times = idx - self.integral.len() as i64 + 1;
-        while times > 0 {
-          b.integral.push(Dbt5);
-          times -= 1;
-        }
-        // # echo b.integral, " ", idx, " times ", times
+    b.add_at_mut(idx, d);
+    b
+  }
 
-        b.integral[idx as usize] = d;
-        b
-      } else {
-        let (carry, unit) = self.integral[idx as usize] + d;
-        b.integral[idx as usize] = unit;
-        // echo "sum: ", sum
-        if carry != Dbt5 {
-          // echo "has carry in integral: ", sum
-          b.add_at(idx + 1, carry)
-        } else {
-          b
-        }
-      }
+  /// like `add_at`, but in place, carries are moved up in a loop
+  pub fn add_at_mut(&mut self, idx: i64, d: DualBalancedTernaryDigit) {
+    let mut idx = idx;
+    let mut carry = d;
+    while carry != Dbt5 {
+      let slot = self.slot_mut(idx);
+      let (next, unit) = *slot + carry;
+      *slot = unit;
+      carry = next;
+      idx += 1;
+    }
+    self.trim_mut();
+  }
+
+  /// digit at a position, filling `5` when digits are not long enough
+  fn slot_mut(&mut self, idx: i64) -> &mut DualBalancedTernaryDigit {
+    let (digits, at) = if idx >= 0 {
+      (&mut self.integral, idx as usize)
     } else {
-      let f_idx = -1 - idx;
-      if f_idx > self.fractional.len() as i64 - 1 {
-        let mut times = f_idx - self.fractional.len() as i64 + 1;
-        while times > 0 {
-          b.fractional.push(Dbt5);
-          times -= 1;
-        }
-        b.fractional[f_idx as usize] = d;
-        b
-      } else {
-        let (carry, unit) = self.fractional[f_idx as usize] + d;
-        b.fractional[f_idx as usize] = unit;
-        if carry != Dbt5 {
-          // echo "has carry in fractional: ", sum
-          b.add_at(idx + 1, carry)
-        } else {
-          b
-        }
-      }
+      (&mut self.fractional, (-1 - idx) as usize)
+    };
+    if at >= digits.len() {
+      digits.resize(at + 1, Dbt5);
     }
+    &mut digits[at]
+  }
+
+  /// adds `other` digit by digit from the lowest position, with one carry kept along the way
+  pub(crate) fn add_digits_mut(&mut self, other: &DualBalancedTernary) {
+    self.merge_digits(other, false)
+  }
+
+  /// like `add_digits_mut`, but subtracts `other`
+  pub(crate) fn sub_digits_mut(&mut self, other: &DualBalancedTernary) {
+    self.merge_digits(other, true)
+  }
+
+  fn merge_digits(&mut self, other: &DualBalancedTernary, negate: bool) {
+    let frac_len = self.fractional.len().max(other.fractional.len());
+    let int_len = self.integral.len().max(other.integral.len());
+    self.fractional.resize(frac_len, Dbt5);
+    self.integral.resize(int_len, Dbt5);
+    let mut carry = Dbt5;
+    let mut step = |slot: &mut DualBalancedTernaryDigit, d: DualBalancedTernaryDigit| {
+      let (c1, unit) = *slot + if negate { -d } else { d };
+      let (c2, unit) = unit + carry;
+      // three digits add up to at most one digit of carry
+      carry = (c1 + c2).1;
+      *slot = unit;
+    };
+    for idx in (0..frac_len).rev() {
+      step(&mut self.fractional[idx], *other.fractional.get(idx).unwrap_or(&Dbt5));
+    }
+    for idx in 0..int_len {
+      step(&mut self.integral[idx], *other.integral.get(idx).unwrap_or(&Dbt5));
+    }
+    if carry != Dbt5 {
+      self.integral.push(carry);
+    }
+    self.trim_mut();
+  }
+
+  /// multiplies every digit by `d` in place, a product and a carry also fit in one digit of carry
+  fn mul_digit_mut(&mut self, d: DualBalancedTernaryDigit) {
+    if d == Dbt5 {
+      self.integral.clear();
+      self.fractional.clear();
+      return;
+    }
+    let mut carry = Dbt5;
+    let mut step = |slot: &mut DualBalancedTernaryDigit| {
+      let (c1, unit) = *slot * d;
+      let (c2, unit) = unit + carry;
+      carry = (c1 + c2).1;
+      *slot = unit;
+    };
+    for slot in self.fractional.iter_mut().rev() {
+      step(slot);
+    }
+    for slot in self.integral.iter_mut() {
+      step(slot);
+    }
+    if carry != Dbt5 {
+      self.integral.push(carry);
+    }
+    self.trim_mut();
   }
 
   /// keep value of 1 direction and flip 3 direction
   pub fn conjugate(&self) -> DualBalancedTernary {
     let mut result = self.to_owned();
-    for (idx, item) in result.integral.to_owned().iter().enumerate() {
-      result.integral[idx] = item.flip_left_right();
-    }
-    for (idx, item) in result.fractional.to_owned().iter().enumerate() {
-      result.fractional[idx] = item.flip_left_right();
+    for item in result.integral.iter_mut().chain(result.fractional.iter_mut()) {
+      *item = item.flip_left_right();
     }
     result
   }
@@ -356,31 +417,19 @@ impl DualBalancedTernary {
   /// value at y direction only contains 1, 5, 9,
   /// value at x direction only contains 7, 5, 3.
   pub fn split_yx(&self) -> (DualBalancedTernary, DualBalancedTernary) {
-    let mut x: DualBalancedTernary = self.to_owned();
-    let mut y: DualBalancedTernary = self.to_owned();
-    for (idx, item) in self.integral.iter().enumerate() {
-      let v = item.split_yx();
-      let (v_x, v_y) = v;
-      x.integral[idx] = v_x;
-      y.integral[idx] = v_y;
-    }
-    for (idx, item) in self.fractional.iter().enumerate() {
-      let v = item.split_yx();
-      let (v_x, v_y) = v;
-      x.fractional[idx] = v_x;
-      y.fractional[idx] = v_y;
-    }
-    (y.strip_empty_tails(), x.strip_empty_tails())
+    let (x_integral, y_integral): (Vec<_>, Vec<_>) = self.integral.iter().map(|d| d.split_yx()).unzip();
+    let (x_fractional, y_fractional): (Vec<_>, Vec<_>) = self.fractional.iter().map(|d| d.split_yx()).unzip();
+    (
+      DualBalancedTernary::from_digits(y_integral, y_fractional),
+      DualBalancedTernary::from_digits(x_integral, x_fractional),
+    )
   }
 
   /// clockwise rotation
   pub fn rotate3(&self) -> DualBalancedTernary {
     let mut result = self.to_owned();
-    for (idx, item) in result.integral.to_owned().iter().enumerate() {
-      result.integral[idx] = item.rotate3();
-    }
-    for (idx, item) in result.fractional.to_owned().iter().enumerate() {
-      result.fractional[idx] = item.rotate3();
+    for item in result.integral.iter_mut().chain(result.fractional.iter_mut()) {
+      *item = item.rotate3();
     }
     result
   }
@@ -388,32 +437,24 @@ impl DualBalancedTernary {
   /// anti-clockwise rotation
   pub fn rotate7(&self) -> DualBalancedTernary {
     let mut result = self.to_owned();
-    for (idx, item) in result.integral.to_owned().iter().enumerate() {
-      result.integral[idx] = item.rotate7();
-    }
-    for (idx, item) in result.fractional.to_owned().iter().enumerate() {
-      result.fractional[idx] = item.rotate7();
+    for item in result.integral.iter_mut().chain(result.fractional.iter_mut()) {
+      *item = item.rotate7();
     }
     result
   }
 
   pub fn get_first_digit(&self) -> (DualBalancedTernaryDigit, i64) {
-    let a2 = self.strip_empty_tails();
-    if !a2.integral.is_empty() {
-      return (a2.integral.last().unwrap().to_owned(), a2.integral.len() as i64 - 1);
-    } else if a2.fractional.is_empty() {
-      (Dbt5, 0)
-    } else {
-      for (idx, item) in a2.fractional.iter().enumerate() {
-        if item != &Dbt5 {
-          return (item.to_owned(), -1 - idx as i64);
-        }
-      }
-      unreachable!("TODO get first")
+    if let Some(idx) = self.integral.iter().rposition(|d| *d != Dbt5) {
+      return (self.integral[idx], idx as i64);
+    }
+    match self.fractional.iter().position(|d| *d != Dbt5) {
+      Some(idx) => (self.fractional[idx], -1 - idx as i64),
+      None => (Dbt5, 0),
     }
   }
 
   /// only works for paths containing 1,5,9
+  #[deprecated(note = "use `cmp_y`, which takes references and works for all values")]
   pub fn linear_greater_than(self, b: DualBalancedTernary) -> bool {
     let delta = self - b;
     let (digit, _) = delta.get_first_digit();
@@ -421,34 +462,48 @@ impl DualBalancedTernary {
   }
 
   /// only works for paths containing 1,5,9
+  #[deprecated(note = "use `cmp_y`, which takes references and works for all values")]
   pub fn linear_littler_than(self, b: DualBalancedTernary) -> bool {
     let delta = self - b;
     let (digit, _) = delta.get_first_digit();
     digit == Dbt9
   }
 
-  /// ternary divide only handles values consisted of 1,5,9
+  /// ternary divide only handles values consisted of 1,5,9, digits are cut off after `DIV_PRECISION * 2` steps,
+  /// panics for `&5` divisors and for values with other digits, see `try_div` for a division that does not panic
   pub fn linear_divide(&self, other: DualBalancedTernary) -> DualBalancedTernary {
-    let mut result = DualBalancedTernary {
-      integral: vec![],
-      fractional: vec![],
-    };
-    // echo fmt"dividing: a b {a} {b}"
-    if self.is_zero() {
-      return self.to_owned();
+    assert!(!other.is_zero(), "{}", DbtError::DivisionByZero);
+    assert!(
+      self.is_linear_ternary() && other.is_linear_ternary(),
+      "{}",
+      DbtError::NonLinearValue
+    );
+    match self.linear_divide_rem(&other, DIV_PRECISION * 2) {
+      Ok((result, _)) => result,
+      Err(e) => unreachable!("checked operands always divide: {}", e),
     }
+  }
+
+  /// long division on values consisted of 1,5,9, returns quotient and the reminder left after `steps` steps
+  fn linear_divide_rem(
+    &self,
+    other: &DualBalancedTernary,
+    steps: usize,
+  ) -> Result<(DualBalancedTernary, DualBalancedTernary), DbtError> {
+    let mut result = ZERO;
+    // echo fmt"dividing: a b {a} {b}"
     if other.is_zero() {
-      unreachable!("&5 is not a valid divisor as divisor")
+      return Err(DbtError::DivisionByZero);
     }
-    if !self.is_linear_ternary() {
-      unreachable!("only linear ternary values allowed for a: {}", self)
+    if self.is_zero() {
+      return Ok((ZERO, ZERO));
     }
-    if !other.is_linear_ternary() {
-      unreachable!("only linear ternary values allowed for b: {}", other)
+    if !self.is_linear_ternary() || !other.is_linear_ternary() {
+      return Err(DbtError::NonLinearValue);
     }
 
     let mut reminder = self.to_owned();
-    let mut precision = DIV_PRECISION * 2;
+    let mut precision = steps;
     // echo fmt"initial: {reminder} {b}"
     while !reminder.is_zero() && precision > 0 {
       // echo fmt"loop with reminder:{reminder} divisor:{b} result:{result}"
@@ -461,24 +516,97 @@ impl DualBalancedTernary {
       } else if (a_digit == Dbt1 && b_digit == Dbt9) || (a_digit == Dbt9 && b_digit == Dbt1) {
         Dbt9
       } else {
-        unreachable!("TODO, unknown case")
+        return Err(DbtError::NonLinearValue);
       };
       let v = ZERO.add_at(try_position, try_digit);
-      let step = v.to_owned() * other.to_owned();
-      reminder = reminder.to_owned() - step;
-      result = result + v;
+      reminder -= &v * other;
+      result += v;
       precision -= 1;
     }
     // echo fmt"temp result: {result}"
-    result
+    Ok((result, reminder))
+  }
+
+  /// balanced long division on values consisted of 1,5,9, settles one digit per position
+  /// from the top down to `min_position`, the reminder is at most half a unit of the last position
+  pub(crate) fn linear_divide_to(
+    &self,
+    other: &DualBalancedTernary,
+    min_position: i64,
+  ) -> Result<(DualBalancedTernary, DualBalancedTernary), DbtError> {
+    if other.is_zero() {
+      return Err(DbtError::DivisionByZero);
+    }
+    if !self.is_linear_ternary() || !other.is_linear_ternary() {
+      return Err(DbtError::NonLinearValue);
+    }
+    let mut result = ZERO;
+    let mut reminder = self.to_owned();
+    if reminder.is_zero() {
+      return Ok((result, reminder));
+    }
+    // quotient is smaller than 3^(a_idx - b_idx + 1)
+    let mut position = reminder.get_first_digit().1 - other.get_first_digit().1 + 1;
+    while position >= min_position && !reminder.is_zero() {
+      let (digit, next) = reminder.linear_divide_digit(other, position);
+      result.add_at_mut(position, digit);
+      reminder = next;
+      position -= 1;
+    }
+    Ok((result, reminder))
+  }
+
+  /// one step of `linear_divide_to` on a reminder, returns the digit settled at `position` and the new reminder
+  pub(crate) fn linear_divide_digit(
+    &self,
+    other: &DualBalancedTernary,
+    position: i64,
+  ) -> (DualBalancedTernaryDigit, DualBalancedTernary) {
+    let (r_digit, _) = self.get_first_digit();
+    let (b_digit, _) = other.get_first_digit();
+    let r_abs = if r_digit == Dbt9 { -self.to_owned() } else { self.to_owned() };
+    let b_abs = if b_digit == Dbt9 { -other.to_owned() } else { other.to_owned() };
+    // a digit is only needed when reminder is more than half of the divisor at this position
+    if (&r_abs + &r_abs).cmp_y(&b_abs.move_by(position)) == Ordering::Greater {
+      let digit = if r_digit == b_digit { Dbt1 } else { Dbt9 };
+      let step = ZERO.add_at(position, digit) * other;
+      (digit, self - step)
+    } else {
+      (Dbt5, self.to_owned())
+    }
+  }
+
+  /// division that reports `&5` divisors and internal failures instead of panicking,
+  /// `is_exact` tells whether the quotient terminated within `DIV_PRECISION * 2` steps of long division
+  pub fn try_quotient(&self, other: &DualBalancedTernary) -> Result<Quotient, DbtError> {
+    if other.is_zero() {
+      return Err(DbtError::DivisionByZero);
+    }
+    let cj = other.conjugate();
+    let a2 = self * &cj;
+    let b2 = other * cj; // support only 1,5,9 in value now
+    let (ax, ay) = a2.split_yx();
+    let (qy, ry) = ay.linear_divide_rem(&b2, DIV_PRECISION * 2)?;
+    let (qx, rx) = ax.rotate7().linear_divide_rem(&b2, DIV_PRECISION * 2)?;
+    Ok(Quotient {
+      value: qy + qx.rotate3(),
+      is_exact: ry.is_zero() && rx.is_zero(),
+    })
+  }
+
+  /// like `/`, but returns an error for `&5` divisors
+  pub fn try_div(&self, other: &DualBalancedTernary) -> Result<DualBalancedTernary, DbtError> {
+    self.try_quotient(other).map(|q| q.value)
+  }
+
+  /// like `/`, but returns `None` for `&5` divisors
+  pub fn checked_div(&self, other: &DualBalancedTernary) -> Option<DualBalancedTernary> {
+    self.try_div(other).ok()
   }
 
   /// drop fractional part
   pub fn round(&self) -> Self {
-    DualBalancedTernary {
-      integral: self.integral.to_owned(),
-      fractional: vec![],
-    }
+    DualBalancedTernary::from_digits(self.integral.to_owned(), vec![])
   }
 
   /// drop fractional part but leave at least n digits
@@ -492,44 +620,38 @@ impl DualBalancedTernary {
         fractional.push(self.fractional[i]);
         i += 1;
       }
-      DualBalancedTernary {
-        integral: self.integral.to_owned(),
-        fractional,
-      }
+      DualBalancedTernary::from_digits(self.integral.to_owned(), fractional)
     }
   }
 
-  // 5 is the zero point of digits, can be removed at end
+  /// values are kept without `5`s at the far ends, so this is a clone
+  #[deprecated(note = "values are always canonical, use `clone`")]
   pub fn strip_empty_tails(&self) -> DualBalancedTernary {
-    if (self.integral.is_empty() || self.integral.to_owned()[self.integral.len() - 1] != Dbt5)
-      && (self.fractional.is_empty() || self.fractional.to_owned()[self.fractional.len() - 1] != Dbt5)
-    {
-      return self.to_owned();
-    }
-    let mut y = self.to_owned();
-    while !y.integral.is_empty() && y.integral.to_owned()[y.integral.len() - 1] == Dbt5 {
-      y.integral.pop();
-    }
-    while !y.fractional.is_empty() && y.fractional.to_owned()[y.fractional.len() - 1] == Dbt5 {
-      y.fractional.pop();
-    }
-    y
+    self.clone()
   }
 
   pub fn pairs(&self) -> Vec<(i64, DualBalancedTernaryDigit)> {
     let mut result: Vec<(i64, DualBalancedTernaryDigit)> = vec![];
-    for (idx, item) in self.integral.to_owned().iter().enumerate() {
-      result.push((idx as i64, item.to_owned()));
+    for (idx, item) in self.integral.iter().enumerate() {
+      result.push((idx as i64, *item));
     }
-    for (idx, item) in self.fractional.to_owned().iter().enumerate() {
-      result.push((-1 - idx as i64, item.to_owned()));
+    for (idx, item) in self.fractional.iter().enumerate() {
+      result.push((-1 - idx as i64, *item));
     }
     result
   }
 
   pub fn is_zero(&self) -> bool {
-    let a2 = self.strip_empty_tails();
-    a2.integral.is_empty() && a2.fractional.is_empty()
+    self.integral.iter().chain(&self.fractional).all(|d| *d == Dbt5)
+  }
+
+  /// sign of a value consisted of 1,5,9, `1` for positive and `-1` for negative
+  pub(crate) fn linear_sign(&self) -> i64 {
+    match self.get_first_digit().0 {
+      Dbt1 => 1,
+      Dbt9 => -1,
+      _ => 0,
+    }
   }
 
   /// internally it relies on 1-directional arithmetic for calculation
@@ -549,7 +671,7 @@ impl DualBalancedTernary {
 }
 
 impl TryFrom<char> for DualBalancedTernaryDigit {
-  type Error = String;
+  type Error = DbtError;
   fn try_from(value: char) -> Result<Self, Self::Error> {
     match value {
       '1' => Ok(DualBalancedTernaryDigit::Dbt1),
@@ -561,68 +683,48 @@ impl TryFrom<char> for DualBalancedTernaryDigit {
       '7' => Ok(DualBalancedTernaryDigit::Dbt7),
       '8' => Ok(DualBalancedTernaryDigit::Dbt8),
       '9' => Ok(DualBalancedTernaryDigit::Dbt9),
-      _ => Err(format!("{} is not valid ternary digit representation", value)),
+      _ => Err(DbtError::InvalidDigitChar(value)),
     }
   }
 }
 
 impl FromStr for DualBalancedTernary {
-  type Err = String;
+  type Err = DbtError;
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    let mut result = DualBalancedTernary {
-      integral: vec![],
-      fractional: vec![],
+    let mut result = ZERO;
+    let content = match s.strip_prefix('&') {
+      Some(content) => content,
+      None => return Err(DbtError::MissingPrefix),
     };
-    if s.is_empty() {
-      return Err(String::from("ternary requires & symbol"));
-    }
-    let content = s[1..].to_string();
     if content.is_empty() {
-      return Err(String::from("ternary requires a number, at least &5"));
+      return Err(DbtError::EmptyNumber);
     }
-    let pieces = content.split('.').collect::<Vec<&str>>();
-    if !pieces.is_empty() {
-      let chunk = pieces[0];
-      for c in chunk.chars() {
-        result.integral.insert(0, c.try_into()?);
+    let mut in_fractional = false;
+    // position counts the leading `&`
+    for (idx, c) in content.chars().enumerate() {
+      if c == '.' {
+        if in_fractional {
+          return Err(DbtError::TooManyRadixPoints);
+        }
+        in_fractional = true;
+        continue;
       }
-    }
-    if pieces.len() == 2 {
-      let chunk = pieces[1];
-      for c in chunk.chars() {
-        result.fractional.push(c.try_into()?);
+      let digit = DualBalancedTernaryDigit::try_from(c).map_err(|_| DbtError::InvalidDigit { ch: c, position: idx + 1 })?;
+      if in_fractional {
+        result.fractional.push(digit);
+      } else {
+        result.integral.insert(0, digit);
       }
     }
-    if pieces.len() > 2 {
-      return Err(format!("invalid format for a ternary value: {}", s));
-    }
-    result = result.strip_empty_tails();
+    result.trim_mut();
     Ok(result)
   }
 }
 
+/// compares digits, values are canonical so equal values have equal digits
 impl PartialEq for DualBalancedTernary {
   fn eq(&self, other: &Self) -> bool {
-    let a2 = self.strip_empty_tails();
-    let b2 = other.strip_empty_tails();
-    if a2.integral.len() != b2.integral.len() {
-      return false;
-    }
-    if a2.fractional.len() != b2.fractional.len() {
-      return false;
-    }
-    for (idx, item) in a2.integral.iter().enumerate() {
-      if &b2.integral[idx] != item {
-        return false;
-      }
-    }
-    for (idx, item) in a2.fractional.iter().enumerate() {
-      if &b2.fractional[idx] != item {
-        return false;
-      }
-    }
-
-    true
+    self.integral == other.integral && self.fractional == other.fractional
   }
 }
 impl Eq for DualBalancedTernary {}
@@ -630,78 +732,438 @@ impl Eq for DualBalancedTernary {}
 impl Hash for DualBalancedTernary {
   fn hash<H: Hasher>(&self, state: &mut H) {
     "DualBalancedTernary".hash(state);
+    self.integral.hash(state);
+    self.fractional.hash(state);
+  }
+}
 
-    let a2 = self.strip_empty_tails();
-    for item in a2.integral {
-      item.hash(state)
-    }
-    (".").hash(state);
-    for item in a2.fractional {
-      item.hash(state)
-    }
+impl Add<&DualBalancedTernary> for DualBalancedTernary {
+  type Output = Self;
+  fn add(mut self, other: &DualBalancedTernary) -> Self {
+    self.add_digits_mut(other);
+    self
   }
 }
 
 impl Add for DualBalancedTernary {
   type Output = Self;
+  fn add(self, other: Self) -> Self {
+    self + &other
+  }
+}
+
+impl Add<DualBalancedTernary> for &DualBalancedTernary {
+  type Output = DualBalancedTernary;
+  fn add(self, other: DualBalancedTernary) -> DualBalancedTernary {
+    other + self
+  }
+}
+
+impl Add for &DualBalancedTernary {
+  type Output = DualBalancedTernary;
+  fn add(self, other: Self) -> DualBalancedTernary {
+    self.to_owned() + other
+  }
+}
+
+impl Sub<&DualBalancedTernary> for DualBalancedTernary {
+  type Output = Self;
+  fn sub(mut self, other: &DualBalancedTernary) -> Self {
+    self.sub_digits_mut(other);
+    self
+  }
+}
+
+impl Sub for DualBalancedTernary {
+  type Output = Self;
+  fn sub(self, other: Self) -> Self {
+    self - &other
+  }
+}
+
+impl Sub<DualBalancedTernary> for &DualBalancedTernary {
+  type Output = DualBalancedTernary;
+  fn sub(self, other: DualBalancedTernary) -> DualBalancedTernary {
+    -other + self
+  }
+}
+
+impl Sub for &DualBalancedTernary {
+  type Output = DualBalancedTernary;
+  fn sub(self, other: Self) -> DualBalancedTernary {
+    self.to_owned() - other
+  }
+}
+
+impl Neg for &DualBalancedTernary {
+  type Output = DualBalancedTernary;
+  fn neg(self) -> DualBalancedTernary {
+    -self.to_owned()
+  }
+}
+
+/// `/` panics for `&5` divisors, see `try_div`
+fn div_or_panic(a: &DualBalancedTernary, b: &DualBalancedTernary) -> DualBalancedTernary {
+  assert!(!b.is_zero(), "{}", DbtError::DivisionByZero);
+  match a.try_div(b) {
+    Ok(v) => v,
+    Err(e) => unreachable!("products with conjugates always divide: {}", e),
+  }
+}
 
-  fn add(self, b: Self) -> Self {
-    let mut a2 = self;
-    for (idx, item) in b.integral.iter().enumerate() {
-      // echo "adding: ", a2, " ", idx, " ", item
-      a2 = a2.add_at(idx as i64, item.to_owned());
+/// operators that read both operands, for all combinations of values and references
+macro_rules! borrowing_binop {
+  ($imp:ident, $method:ident, $f:expr) => {
+    impl $imp for DualBalancedTernary {
+      type Output = Self;
+      fn $method(self, other: Self) -> Self {
+        $f(&self, &other)
+      }
+    }
+
+    impl $imp<&DualBalancedTernary> for DualBalancedTernary {
+      type Output = Self;
+      fn $method(self, other: &DualBalancedTernary) -> Self {
+        $f(&self, other)
+      }
+    }
+
+    impl $imp<DualBalancedTernary> for &DualBalancedTernary {
+      type Output = DualBalancedTernary;
+      fn $method(self, other: DualBalancedTernary) -> DualBalancedTernary {
+        $f(self, &other)
+      }
     }
-    for (idx, item) in b.fractional.iter().enumerate() {
-      // echo "f adding: ", a2, " ", idx, " ", item
-      a2 = a2.add_at(-1 - idx as i64, item.to_owned());
+
+    impl $imp for &DualBalancedTernary {
+      type Output = DualBalancedTernary;
+      fn $method(self, other: Self) -> DualBalancedTernary {
+        $f(self, other)
+      }
     }
-    // echo "result: ", a2
-    a2.strip_empty_tails();
-    a2
+  };
+}
+
+// digit by digit for short values, and by Karatsuba splitting for long ones
+borrowing_binop!(Mul, mul, DualBalancedTernary::mul_digits);
+borrowing_binop!(Div, div, div_or_panic);
+
+impl AddAssign<&DualBalancedTernary> for DualBalancedTernary {
+  fn add_assign(&mut self, other: &DualBalancedTernary) {
+    self.add_digits_mut(other);
   }
 }
 
-impl Sub for DualBalancedTernary {
+impl AddAssign for DualBalancedTernary {
+  fn add_assign(&mut self, other: Self) {
+    self.add_digits_mut(&other);
+  }
+}
+
+impl SubAssign<&DualBalancedTernary> for DualBalancedTernary {
+  fn sub_assign(&mut self, other: &DualBalancedTernary) {
+    self.sub_digits_mut(other);
+  }
+}
+
+impl SubAssign for DualBalancedTernary {
+  fn sub_assign(&mut self, other: Self) {
+    self.sub_digits_mut(&other);
+  }
+}
+
+impl MulAssign<&DualBalancedTernary> for DualBalancedTernary {
+  fn mul_assign(&mut self, other: &DualBalancedTernary) {
+    *self = self.mul_digits(other);
+  }
+}
+
+impl MulAssign for DualBalancedTernary {
+  fn mul_assign(&mut self, other: Self) {
+    *self = self.mul_digits(&other);
+  }
+}
+
+impl DivAssign<&DualBalancedTernary> for DualBalancedTernary {
+  fn div_assign(&mut self, other: &DualBalancedTernary) {
+    *self = div_or_panic(self, other);
+  }
+}
+
+impl DivAssign for DualBalancedTernary {
+  fn div_assign(&mut self, other: Self) {
+    *self = div_or_panic(self, &other);
+  }
+}
+
+/// adds a digit at unit position
+impl Add<DualBalancedTernaryDigit> for DualBalancedTernary {
+  type Output = Self;
+  fn add(mut self, d: DualBalancedTernaryDigit) -> Self {
+    self.add_at_mut(0, d);
+    self
+  }
+}
+
+impl Add<DualBalancedTernaryDigit> for &DualBalancedTernary {
+  type Output = DualBalancedTernary;
+  fn add(self, d: DualBalancedTernaryDigit) -> DualBalancedTernary {
+    self.add_at(0, d)
+  }
+}
+
+impl AddAssign<DualBalancedTernaryDigit> for DualBalancedTernary {
+  fn add_assign(&mut self, d: DualBalancedTernaryDigit) {
+    self.add_at_mut(0, d);
+  }
+}
+
+impl Sub<DualBalancedTernaryDigit> for DualBalancedTernary {
   type Output = Self;
-  fn sub(self, other: Self) -> Self::Output {
-    self.add(-other)
+  fn sub(mut self, d: DualBalancedTernaryDigit) -> Self {
+    self.add_at_mut(0, -d);
+    self
   }
 }
 
-impl Mul for DualBalancedTernary {
+impl Sub<DualBalancedTernaryDigit> for &DualBalancedTernary {
+  type Output = DualBalancedTernary;
+  fn sub(self, d: DualBalancedTernaryDigit) -> DualBalancedTernary {
+    self.add_at(0, -d)
+  }
+}
+
+impl SubAssign<DualBalancedTernaryDigit> for DualBalancedTernary {
+  fn sub_assign(&mut self, d: DualBalancedTernaryDigit) {
+    self.add_at_mut(0, -d);
+  }
+}
+
+/// multiplies every digit, `x * Dbt3` rotates `x` by a quarter turn
+impl Mul<DualBalancedTernaryDigit> for DualBalancedTernary {
   type Output = Self;
+  fn mul(mut self, d: DualBalancedTernaryDigit) -> Self {
+    self.mul_digit_mut(d);
+    self
+  }
+}
 
-  fn mul(self, other: Self) -> Self::Output {
-    let mut result = DualBalancedTernary {
-      integral: vec![],
-      fractional: vec![],
-    };
-    for (a_idx, a_item) in self.pairs() {
-      for (b_idx, b_item) in other.pairs() {
-        let (carry, unit) = a_item * b_item;
-        result = result.add_at(a_idx + b_idx, unit);
-        if carry != Dbt5 {
-          result = result.add_at(a_idx + b_idx + 1, carry);
+impl Mul<DualBalancedTernaryDigit> for &DualBalancedTernary {
+  type Output = DualBalancedTernary;
+  fn mul(self, d: DualBalancedTernaryDigit) -> DualBalancedTernary {
+    self.to_owned() * d
+  }
+}
+
+impl MulAssign<DualBalancedTernaryDigit> for DualBalancedTernary {
+  fn mul_assign(&mut self, d: DualBalancedTernaryDigit) {
+    self.mul_digit_mut(d);
+  }
+}
+
+impl DualBalancedTernary {
+  /// Gaussian prime test, values with fractional digits are not primes.
+  /// panics when trial division passes `TRIAL_DIVISION_LIMIT` before deciding, see `try_is_prime`
+  pub fn is_prime(&self) -> bool {
+    match self.try_is_prime() {
+      Ok(v) => v,
+      Err(e) => panic!("{}", e),
+    }
+  }
+
+  /// like `is_prime`, but returns an error when trial division passes `TRIAL_DIVISION_LIMIT` before deciding
+  pub fn try_is_prime(&self) -> Result<bool, DbtError> {
+    match self.integer_xy() {
+      Ok((0, v)) | Ok((v, 0)) => {
+        let v = v.unsigned_abs();
+        Ok(v % 4 == 3 && is_rational_prime(v)?)
+      }
+      Ok((x, y)) => match norm_xy(x, y) {
+        Some(n) => is_rational_prime(n),
+        None => Ok(false),
+      },
+      Err(_) => Ok(false),
+    }
+  }
+
+  /// all primes with norm up to `max_norm` with a sieve,
+  /// in the form of `normalize_associate`, ordered by norm.
+  /// the sieve takes `max_norm + 1` bytes and `O(max_norm * log(log(max_norm)))` steps, so keep `max_norm` small
+  pub fn primes_within(max_norm: u64) -> Vec<DualBalancedTernary> {
+    let limit = max_norm as usize;
+    let mut sieve = vec![true; limit + 1];
+    let mut found: Vec<(u64, i128, i128)> = vec![];
+    for p in 2..=limit {
+      if !sieve[p] {
+        continue;
+      }
+      if let Some(square) = p.checked_mul(p) {
+        for k in (square..=limit).step_by(p) {
+          sieve[k] = false;
+        }
+      }
+      let p128 = p as i128;
+      match p % 4 {
+        2 => found.push((2, 1, 1)),
+        1 => {
+          let (a, b) = two_squares(p128);
+          found.push((p as u64, a, b));
+          found.push((p as u64, b, a));
+        }
+        _ => {
+          if let Some(n) = (p as u64).checked_mul(p as u64) {
+            if n <= max_norm {
+              found.push((n, 0, p128));
+            }
+          }
         }
-        // echo fmt"multiply a:{a_item} b:{b_item}, v:{v}, result:{result}"
       }
     }
-    result
+    found.sort();
+    found
+      .into_iter()
+      .map(|(_, x, y)| DualBalancedTernary::from_integer_xy(x, y))
+      .collect()
+  }
+
+  /// prime factors with multiplicities, in the form of `normalize_associate`,
+  /// multiplied by the unit they give back `self`. relies on trial division up to `TRIAL_DIVISION_LIMIT`,
+  /// and returns an error when the norm has a factor left that is too large to decide
+  pub fn factorize(&self) -> Result<Factorization, DbtError> {
+    let (mut x, mut y) = self.integer_xy()?;
+    if x == 0 && y == 0 {
+      return Err(DbtError::ZeroValue);
+    }
+    let mut rest = norm_xy(x, y).ok_or(DbtError::IntegerOverflow)?;
+    let mut rational_primes: Vec<u128> = vec![];
+    let mut d: u128 = 2;
+    while d <= rest / d {
+      if d > TRIAL_DIVISION_LIMIT {
+        return Err(DbtError::TrialDivisionLimit);
+      }
+      if rest.is_multiple_of(d) {
+        rational_primes.push(d);
+        while rest.is_multiple_of(d) {
+          rest /= d;
+        }
+      }
+      d += 1;
+    }
+    if rest > 1 {
+      rational_primes.push(rest);
+    }
+
+    let mut found: Vec<(u128, i128, i128, u32)> = vec![];
+    for p in rational_primes {
+      let p128 = p as i128;
+      let candidates = match p % 4 {
+        2 => vec![(1, 1)],
+        1 => {
+          let (a, b) = two_squares(p128);
+          vec![(a, b), (b, a)]
+        }
+        _ => vec![(0, p128)],
+      };
+      for (px, py) in candidates {
+        let mut count = 0;
+        while let Some((qx, qy)) = exact_div_xy((x, y), (px, py))? {
+          x = qx;
+          y = qy;
+          count += 1;
+        }
+        if count > 0 {
+          found.push((p, px, py, count));
+        }
+      }
+    }
+    found.sort();
+    Ok(Factorization {
+      unit: DualBalancedTernary::from_integer_xy(x, y),
+      primes: found
+        .into_iter()
+        .map(|(_, px, py, count)| (DualBalancedTernary::from_integer_xy(px, py), count))
+        .collect(),
+    })
   }
 }
 
-impl Div for DualBalancedTernary {
-  type Output = Self;
+/// `unit` times every prime raised to its multiplicity, `unit` is one of `&1`, `&3`, `&9`, `&7`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Factorization {
+  pub unit: DualBalancedTernary,
+  pub primes: Vec<(DualBalancedTernary, u32)>,
+}
 
-  fn div(self, other: DualBalancedTernary) -> Self {
-    let cj = other.conjugate();
-    let a2 = self * cj.to_owned();
-    let b2 = other * cj; // support only 1,5,9 in value now
-    let (x, y) = a2.split_yx();
-    let ay = y;
-    let ax = x;
-    // echo fmt"b.. {b} {cj} => {b2}"
-    // echo fmt"splitted: {splitted} from {a2}, b2 is {b2}"
-    ay.linear_divide(b2.to_owned()) + (ax.rotate7().linear_divide(b2).rotate3())
+/// written like `&9 * &8^2 * &16`, unit `&1` is omitted when there are primes
+impl fmt::Display for Factorization {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let mut parts: Vec<String> = vec![];
+    if self.primes.is_empty() || self.unit.integral != [Dbt1] {
+      parts.push(format!("{}", self.unit));
+    }
+    for (p, count) in &self.primes {
+      if *count == 1 {
+        parts.push(format!("{}", p));
+      } else {
+        parts.push(format!("{}^{}", p, count));
+      }
+    }
+    write!(f, "{}", parts.join(" * "))
+  }
+}
+
+fn norm_xy(x: i128, y: i128) -> Option<u128> {
+  let x = x.unsigned_abs();
+  let y = y.unsigned_abs();
+  x.checked_mul(x)?.checked_add(y.checked_mul(y)?)
+}
+
+/// trial division, fails when no factor is found up to `TRIAL_DIVISION_LIMIT` and `n` is past its square
+fn is_rational_prime(n: u128) -> Result<bool, DbtError> {
+  if n < 2 {
+    return Ok(false);
+  }
+  let mut d: u128 = 2;
+  while d <= n / d {
+    if d > TRIAL_DIVISION_LIMIT {
+      return Err(DbtError::TrialDivisionLimit);
+    }
+    if n.is_multiple_of(d) {
+      return Ok(false);
+    }
+    d += 1;
+  }
+  Ok(true)
+}
+
+/// `(a, b)` with `a * a + b * b = p` and `a > b > 0`, for primes of `4k + 1`
+fn two_squares(p: i128) -> (i128, i128) {
+  let mut b: i128 = 1;
+  while 2 * b * b < p {
+    let rest = p - b * b;
+    let a = (rest as f64).sqrt() as i128;
+    for a in [a - 1, a, a + 1] {
+      if a * a == rest {
+        return (a, b);
+      }
+    }
+    b += 1;
+  }
+  unreachable!("{} is not a sum of two squares", p)
+}
+
+/// `a / b` in `(x, y)` coordinates, `None` when the quotient is not an integer
+fn exact_div_xy(a: (i128, i128), b: (i128, i128)) -> Result<Option<(i128, i128)>, DbtError> {
+  let (ax, ay) = a;
+  let (bx, by) = b;
+  let mul = |p: i128, q: i128| p.checked_mul(q).ok_or(DbtError::IntegerOverflow);
+  // a * conj(b), where `1` direction is real and `3` direction is imaginary
+  let y = mul(ay, by)?.checked_add(mul(ax, bx)?).ok_or(DbtError::IntegerOverflow)?;
+  let x = mul(ax, by)?.checked_sub(mul(ay, bx)?).ok_or(DbtError::IntegerOverflow)?;
+  let n = mul(bx, bx)?.checked_add(mul(by, by)?).ok_or(DbtError::IntegerOverflow)?;
+  if y % n == 0 && x % n == 0 {
+    Ok(Some((x / n, y / n)))
+  } else {
+    Ok(None)
   }
 }
@@ -0,0 +1,141 @@
+//! Exact fractions with DBT integers as numerator and denominator.
+
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::context::ArithmeticContext;
+use crate::error::DbtError;
+use crate::primes::{DualBalancedTernary, ONE};
+use crate::repeating::RepeatingTernary;
+
+/// `num / den` kept exact under `+ - * /`,
+/// always reduced by GCD, with denominator in its associate form having positive `1` side
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DbtRational {
+  num: DualBalancedTernary,
+  den: DualBalancedTernary,
+}
+
+impl DbtRational {
+  /// fractional digits in either value are moved into integral part first
+  pub fn new(num: DualBalancedTernary, den: DualBalancedTernary) -> Result<Self, DbtError> {
+    if den.is_zero() {
+      return Err(DbtError::DivisionByZero);
+    }
+    let shift = num.fractional.len().max(den.fractional.len());
+    let num = num.move_by(shift as i64);
+    let den = den.move_by(shift as i64);
+
+    let g = num.gcd(&den);
+    // divisions by GCD are exact
+    let num = num.nearest_div(&g)?;
+    let den = den.nearest_div(&g)?;
+    let (den, unit) = den.associate_parts();
+    Ok(DbtRational { num: num * unit, den })
+  }
+
+  pub fn numerator(&self) -> &DualBalancedTernary {
+    &self.num
+  }
+
+  pub fn denominator(&self) -> &DualBalancedTernary {
+    &self.den
+  }
+
+  pub fn is_zero(&self) -> bool {
+    self.num.is_zero()
+  }
+
+  /// `true` when denominator is `&1`
+  pub fn is_integral(&self) -> bool {
+    self.den == *ONE
+  }
+
+  /// `1 / self`, returns an error for `&5`
+  pub fn recip(&self) -> Result<Self, DbtError> {
+    DbtRational::new(self.den.to_owned(), self.num.to_owned())
+  }
+
+  /// like `/`, but returns an error for `&5` divisors
+  pub fn try_div(&self, other: &DbtRational) -> Result<Self, DbtError> {
+    DbtRational::new(&self.num * &other.den, &self.den * &other.num)
+  }
+
+  /// value with `ctx.precision` digits in fractional part
+  pub fn to_ternary(&self, ctx: &ArithmeticContext) -> DualBalancedTernary {
+    match self.num.div_with(&self.den, ctx) {
+      Ok(v) => v,
+      Err(e) => unreachable!("denominator is never &5: {}", e),
+    }
+  }
+
+  /// exact value in repeating form, fails when the period is too long to find
+  pub fn to_repeating(&self) -> Result<RepeatingTernary, DbtError> {
+    self.num.div_repeating(&self.den)
+  }
+
+  fn from_parts(num: DualBalancedTernary, den: DualBalancedTernary) -> Self {
+    match DbtRational::new(num, den) {
+      Ok(v) => v,
+      Err(e) => unreachable!("denominator is never &5: {}", e),
+    }
+  }
+}
+
+impl From<DualBalancedTernary> for DbtRational {
+  fn from(x: DualBalancedTernary) -> Self {
+    DbtRational::from_parts(x, ONE.clone())
+  }
+}
+
+/// written like `&1/&19`, or only numerator when denominator is `&1`
+impl fmt::Display for DbtRational {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    if self.is_integral() {
+      write!(f, "{}", self.num)
+    } else {
+      write!(f, "{}/{}", self.num, self.den)
+    }
+  }
+}
+
+impl Neg for DbtRational {
+  type Output = Self;
+  fn neg(self) -> Self {
+    DbtRational {
+      num: -self.num,
+      den: self.den,
+    }
+  }
+}
+
+impl Add for DbtRational {
+  type Output = Self;
+  fn add(self, other: Self) -> Self {
+    DbtRational::from_parts(self.num * &other.den + other.num * &self.den, self.den * other.den)
+  }
+}
+
+impl Sub for DbtRational {
+  type Output = Self;
+  fn sub(self, other: Self) -> Self {
+    self.add(-other)
+  }
+}
+
+impl Mul for DbtRational {
+  type Output = Self;
+  fn mul(self, other: Self) -> Self {
+    DbtRational::from_parts(self.num * other.num, self.den * other.den)
+  }
+}
+
+impl Div for DbtRational {
+  type Output = Self;
+  fn div(self, other: Self) -> Self {
+    match self.try_div(&other) {
+      Ok(v) => v,
+      Err(e) => panic!("{}", e),
+    }
+  }
+}
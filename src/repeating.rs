@@ -0,0 +1,224 @@
+//! Exact values with a repeating fractional part, written like `&1.65(3732)`.
+//!
+//! Quotients of finite DBT values always end up in a repeating pattern of digits,
+//! so `div_repeating` can return them without losing anything.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::digit::{DualBalancedTernaryDigit, DualBalancedTernaryDigit::*};
+use crate::error::DbtError;
+use crate::primes::DualBalancedTernary;
+
+/// default limit of fractional digits `div_repeating` generates before giving up on finding the period
+pub const MAX_REPEATING_DIGITS: usize = 2048;
+
+/// a DBT value whose fractional digits repeat `period` forever after the non-repeating digits,
+/// kept with the shortest period that starts as early as possible
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RepeatingTernary {
+  /// integral part, digits near 0 are placed first
+  integral: Vec<DualBalancedTernaryDigit>,
+  /// fractional digits before the period starts
+  fractional: Vec<DualBalancedTernaryDigit>,
+  /// digits repeated after `fractional`, empty for finite values
+  period: Vec<DualBalancedTernaryDigit>,
+}
+
+impl RepeatingTernary {
+  fn from_parts(
+    mut integral: Vec<DualBalancedTernaryDigit>,
+    mut fractional: Vec<DualBalancedTernaryDigit>,
+    mut period: Vec<DualBalancedTernaryDigit>,
+  ) -> Self {
+    // shortest pattern that builds the period
+    let mut size = 1;
+    while size < period.len() {
+      if period.len().is_multiple_of(size) && (size..period.len()).all(|i| period[i] == period[i - size]) {
+        break;
+      }
+      size += 1;
+    }
+    period.truncate(size);
+    if period == [Dbt5] {
+      period.clear();
+    }
+    // move start of the period earlier when the last non-repeating digit matches
+    while !period.is_empty() && fractional.last() == period.last() {
+      fractional.pop();
+      period.rotate_right(1);
+    }
+    if period.is_empty() {
+      while fractional.last() == Some(&Dbt5) {
+        fractional.pop();
+      }
+    }
+    while integral.last() == Some(&Dbt5) {
+      integral.pop();
+    }
+    RepeatingTernary {
+      integral,
+      fractional,
+      period,
+    }
+  }
+
+  /// `true` when there are no repeating digits
+  pub fn is_finite(&self) -> bool {
+    self.period.is_empty()
+  }
+
+  /// digits repeated forever, empty for finite values
+  pub fn period(&self) -> &[DualBalancedTernaryDigit] {
+    &self.period
+  }
+
+  /// the value before the period, i.e. with repeating digits dropped
+  pub fn non_repeating(&self) -> DualBalancedTernary {
+    DualBalancedTernary::from_digits(self.integral.to_owned(), self.fractional.to_owned())
+  }
+
+  /// expands the period to get `precision` digits in fractional part
+  pub fn to_ternary(&self, precision: usize) -> DualBalancedTernary {
+    let mut fractional = self.fractional.to_owned();
+    fractional.truncate(precision);
+    if !self.period.is_empty() {
+      while fractional.len() < precision {
+        fractional.push(self.period[(fractional.len() - self.fractional.len()) % self.period.len()]);
+      }
+    }
+    DualBalancedTernary::from_digits(self.integral.to_owned(), fractional)
+  }
+}
+
+impl From<DualBalancedTernary> for RepeatingTernary {
+  fn from(x: DualBalancedTernary) -> Self {
+    RepeatingTernary::from_parts(x.integral, x.fractional, vec![])
+  }
+}
+
+/// like `DualBalancedTernary`, with repeating digits in parentheses
+impl fmt::Display for RepeatingTernary {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    if self.period.is_empty() {
+      return write!(f, "{}", self.non_repeating());
+    }
+    write!(f, "&")?;
+    for x in self.integral.iter().rev() {
+      write!(f, "{}", x)?;
+    }
+    // `5`s before the period hold positions, so they are all written
+    write!(f, ".")?;
+    for x in &self.fractional {
+      write!(f, "{}", x)?;
+    }
+    write!(f, "(")?;
+    for x in &self.period {
+      write!(f, "{}", x)?;
+    }
+    write!(f, ")")
+  }
+}
+
+impl FromStr for RepeatingTernary {
+  type Err = DbtError;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (finite, period) = match s.find('(') {
+      Some(idx) => {
+        let rest = &s[idx + 1..];
+        let content = match rest.strip_suffix(')') {
+          Some(content) => content,
+          None => return Err(DbtError::UnclosedPeriod),
+        };
+        // `idx` is a byte offset, positions in errors count characters
+        let position = s[..idx].chars().count();
+        if !s[..idx].contains('.') {
+          return Err(DbtError::InvalidDigit { ch: '(', position });
+        }
+        let mut period = vec![];
+        for (i, c) in content.chars().enumerate() {
+          period.push(DualBalancedTernaryDigit::try_from(c).map_err(|_| DbtError::InvalidDigit {
+            ch: c,
+            position: position + 1 + i,
+          })?);
+        }
+        if period.is_empty() {
+          return Err(DbtError::UnclosedPeriod);
+        }
+        (&s[..idx], period)
+      }
+      None => (s, vec![]),
+    };
+    // `5`s before the period hold positions, count them before they get stripped by parsing
+    let fractional_len = finite.split_once('.').map(|(_, f)| f.chars().count()).unwrap_or(0);
+    let value: DualBalancedTernary = finite.parse()?;
+    let mut fractional = value.fractional;
+    fractional.resize(fractional_len, Dbt5);
+    Ok(RepeatingTernary::from_parts(value.integral, fractional, period))
+  }
+}
+
+/// digit from two linear digits, `y` on `1` direction and `x` turned into `1` direction
+fn combine_linear(y: DualBalancedTernaryDigit, x: DualBalancedTernaryDigit) -> DualBalancedTernaryDigit {
+  // the two parts are orthogonal, no carry
+  (y + x.rotate3()).1
+}
+
+impl DualBalancedTernary {
+  /// exact division, digits are generated until the reminders repeat,
+  /// periods can get very long for divisors with large norms, see `div_repeating_within`
+  pub fn div_repeating(&self, other: &DualBalancedTernary) -> Result<RepeatingTernary, DbtError> {
+    self.div_repeating_within(other, MAX_REPEATING_DIGITS)
+  }
+
+  /// like `div_repeating`, but gives up when the period is not found within `max_digits` fractional digits
+  pub fn div_repeating_within(&self, other: &DualBalancedTernary, max_digits: usize) -> Result<RepeatingTernary, DbtError> {
+    if other.is_zero() {
+      return Err(DbtError::DivisionByZero);
+    }
+    let cj = other.conjugate();
+    let a2 = self * &cj;
+    // norm of divisor, it's positive and only contains 1,5,9
+    let b2 = other * cj;
+    let (ax, mut ry) = a2.split_yx();
+    let mut rx = ax.rotate7();
+    if !ry.is_linear_ternary() || !rx.is_linear_ternary() {
+      return Err(DbtError::NonLinearValue);
+    }
+
+    // quotient is smaller than 3^(a_idx - b_idx + 1)
+    let b_idx = b2.get_first_digit().1;
+    let top = ry.get_first_digit().1.max(rx.get_first_digit().1) - b_idx + 1;
+    let mut integral = vec![Dbt5; top.max(0) as usize + 1];
+    for position in (0..=top.max(0)).rev() {
+      let (dy, next_y) = ry.linear_divide_digit(&b2, position);
+      let (dx, next_x) = rx.linear_divide_digit(&b2, position);
+      integral[position as usize] = combine_linear(dy, dx);
+      ry = next_y;
+      rx = next_x;
+    }
+
+    // the next digit only depends on reminders scaled to current position, a repeated pair starts a period
+    let mut seen: HashMap<(DualBalancedTernary, DualBalancedTernary), usize> = HashMap::new();
+    let mut fractional = vec![];
+    loop {
+      let shift = fractional.len() as i64;
+      let state = (ry.move_by(shift), rx.move_by(shift));
+      if let Some(start) = seen.get(&state) {
+        let period = fractional.split_off(*start);
+        return Ok(RepeatingTernary::from_parts(integral, fractional, period));
+      }
+      if fractional.len() >= max_digits {
+        return Err(DbtError::PeriodTooLong(max_digits));
+      }
+      seen.insert(state, fractional.len());
+      let position = -1 - fractional.len() as i64;
+      let (dy, next_y) = ry.linear_divide_digit(&b2, position);
+      let (dx, next_x) = rx.linear_divide_digit(&b2, position);
+      fractional.push(combine_linear(dy, dx));
+      ry = next_y;
+      rx = next_x;
+    }
+  }
+}
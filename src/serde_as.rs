@@ -0,0 +1,144 @@
+//! Serde support, behind `serde` feature.
+//!
+//! `DualBalancedTernary` is written as the `&1.1` string in human readable formats,
+//! and as the packed buffer of `codec` in binary formats. Digits are written as numbers `1`-`9`.
+//! Modules here pick one form for a field, like `#[serde(with = "dual_balanced_ternary::serde_as::xy")]`.
+
+use std::convert::TryFrom;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::digit::DualBalancedTernaryDigit;
+use crate::primes::DualBalancedTernary;
+
+impl Serialize for DualBalancedTernary {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+      string::serialize(self, serializer)
+    } else {
+      bytes::serialize(self, serializer)
+    }
+  }
+}
+
+impl<'de> Deserialize<'de> for DualBalancedTernary {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    if deserializer.is_human_readable() {
+      string::deserialize(deserializer)
+    } else {
+      bytes::deserialize(deserializer)
+    }
+  }
+}
+
+impl Serialize for DualBalancedTernaryDigit {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u8(u8::from(*self))
+  }
+}
+
+impl<'de> Deserialize<'de> for DualBalancedTernaryDigit {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let n = u8::deserialize(deserializer)?;
+    DualBalancedTernaryDigit::try_from(n).map_err(serde::de::Error::custom)
+  }
+}
+
+/// the `&1.1` string in every format
+pub mod string {
+  use std::fmt;
+  use std::str::FromStr;
+
+  use serde::de::{self, Visitor};
+  use serde::{Deserializer, Serializer};
+
+  use crate::primes::DualBalancedTernary;
+
+  pub fn serialize<S: Serializer>(value: &DualBalancedTernary, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+  }
+
+  pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DualBalancedTernary, D::Error> {
+    deserializer.deserialize_str(StrVisitor)
+  }
+
+  struct StrVisitor;
+
+  impl Visitor<'_> for StrVisitor {
+    type Value = DualBalancedTernary;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "a dbt string like &1.1")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+      DualBalancedTernary::from_str(s).map_err(E::custom)
+    }
+  }
+}
+
+/// the packed buffer of `codec` in every format, buffers in other formats of `codec` are also read
+pub mod bytes {
+  use std::fmt;
+
+  use serde::de::{self, SeqAccess, Visitor};
+  use serde::{Deserializer, Serializer};
+
+  use crate::codec;
+  use crate::primes::DualBalancedTernary;
+
+  pub fn serialize<S: Serializer>(value: &DualBalancedTernary, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(&codec::encode_packed(value))
+  }
+
+  pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DualBalancedTernary, D::Error> {
+    deserializer.deserialize_bytes(BytesVisitor)
+  }
+
+  struct BytesVisitor;
+
+  impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = DualBalancedTernary;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "a dbt buffer")
+    }
+
+    fn visit_bytes<E: de::Error>(self, buf: &[u8]) -> Result<Self::Value, E> {
+      codec::decode(buf).map_err(E::custom)
+    }
+
+    /// formats without bytes, like JSON, write them as a sequence
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+      let mut buf: Vec<u8> = vec![];
+      while let Some(x) = seq.next_element::<u8>()? {
+        buf.push(x);
+      }
+      self.visit_bytes(&buf)
+    }
+  }
+}
+
+/// an `{x, y}` object of floats, like `DualBalancedTernary::new`, fractional digits are limited by `DIV_PRECISION`
+pub mod xy {
+  use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+  use crate::complex::ComplexXy;
+  use crate::primes::DualBalancedTernary;
+
+  #[derive(Serialize, Deserialize)]
+  struct Xy {
+    x: f64,
+    y: f64,
+  }
+
+  pub fn serialize<S: Serializer>(value: &DualBalancedTernary, serializer: S) -> Result<S::Ok, S::Error> {
+    let c = ComplexXy::from(value.to_owned());
+    Xy { x: c.x, y: c.y }.serialize(serializer)
+  }
+
+  pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DualBalancedTernary, D::Error> {
+    let v = Xy::deserialize(deserializer)?;
+    DualBalancedTernary::try_new(v.x, v.y).map_err(de::Error::custom)
+  }
+}
@@ -0,0 +1,119 @@
+//! Streams of DBT values, each value framed by the lengths in its own header.
+//!
+//! Values are written in packed format, see `codec`, and both packed and nibble values are read back.
+
+use std::io::{self, Read, Write};
+use std::iter::FusedIterator;
+
+use crate::codec;
+use crate::error::DbtError;
+use crate::primes::DualBalancedTernary;
+
+/// a LEB128 length of `usize` takes no more bytes than this
+const MAX_VARINT_BYTES: usize = 10;
+
+/// writes values one after another
+pub struct DbtWriter<W: Write> {
+  inner: W,
+}
+
+impl<W: Write> DbtWriter<W> {
+  pub fn new(inner: W) -> Self {
+    DbtWriter { inner }
+  }
+
+  pub fn write(&mut self, value: &DualBalancedTernary) -> io::Result<()> {
+    self.inner.write_all(&codec::encode_packed(value))
+  }
+
+  pub fn flush(&mut self) -> io::Result<()> {
+    self.inner.flush()
+  }
+
+  pub fn into_inner(self) -> W {
+    self.inner
+  }
+}
+
+/// reads values written by `DbtWriter`, or nibble buffers put one after another.
+/// `&[u8]` implements `Read`, so slices are read in place
+pub struct DbtReader<R: Read> {
+  inner: R,
+  /// set by the iterator at the end of the stream or after an error, since the next frame can not be found then
+  done: bool,
+}
+
+impl<R: Read> DbtReader<R> {
+  pub fn new(inner: R) -> Self {
+    DbtReader { inner, done: false }
+  }
+
+  /// `None` when the stream ends between values, an `UnexpectedEof` error when it ends inside one
+  pub fn read(&mut self) -> io::Result<Option<DualBalancedTernary>> {
+    let mut frame = vec![0u8; 2];
+    // retried on `Interrupted` like `read_exact` does
+    loop {
+      match self.inner.read(&mut frame[..1]) {
+        Ok(0) => return Ok(None),
+        Ok(_) => break,
+        Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+        Err(e) => return Err(e),
+      }
+    }
+    self.inner.read_exact(&mut frame[1..])?;
+    if frame != codec::NIBBLE_HEADER && frame != codec::PACKED_MAGIC {
+      return Err(DbtError::UnknownFormat.into());
+    }
+    let int_len = self.read_varint(&mut frame)?;
+    let frac_len = self.read_varint(&mut frame)?;
+    let body = codec::body_length(&frame[..2], int_len, frac_len)?;
+    let start = frame.len();
+    // not trusting the lengths to allocate ahead
+    (&mut self.inner).take(body as u64).read_to_end(&mut frame)?;
+    if frame.len() - start < body {
+      return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    let (value, _) = codec::decode_prefix(&frame)?;
+    Ok(Some(value))
+  }
+
+  /// reads bytes of a varint into `frame`, and parses them
+  fn read_varint(&mut self, frame: &mut Vec<u8>) -> io::Result<usize> {
+    let start = frame.len();
+    let mut byte = [0u8; 1];
+    loop {
+      self.inner.read_exact(&mut byte)?;
+      frame.push(byte[0]);
+      if byte[0] & 0x80 == 0 {
+        break;
+      }
+      if frame.len() - start >= MAX_VARINT_BYTES {
+        return Err(DbtError::InvalidVarint(start).into());
+      }
+    }
+    let (n, _) = codec::read_varint(frame, start)?;
+    Ok(n)
+  }
+
+  pub fn into_inner(self) -> R {
+    self.inner
+  }
+}
+
+/// stops after the first error, as the stream is no longer at the start of a value
+impl<R: Read> Iterator for DbtReader<R> {
+  type Item = io::Result<DualBalancedTernary>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.done {
+      return None;
+    }
+    let item = self.read().transpose();
+    if !matches!(item, Some(Ok(_))) {
+      self.done = true;
+    }
+    item
+  }
+}
+
+impl<R: Read> FusedIterator for DbtReader<R> {}
@@ -0,0 +1,77 @@
+#![cfg(feature = "num-bigint")]
+
+extern crate dual_balanced_ternary;
+
+use std::convert::TryFrom;
+
+use num_bigint::BigInt;
+
+use dual_balanced_ternary::{ternary, DbtError, DualBalancedTernary, DualBalancedTernaryDigit};
+
+/// digits from a linear congruential generator, to get long values without a rng crate
+fn long_value(seed: u64, integral: usize, fractional: usize) -> DualBalancedTernary {
+  let mut state = seed;
+  let mut digit = || {
+    state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    DualBalancedTernaryDigit::try_from((state >> 33) as u8 % 9 + 1).unwrap()
+  };
+  let mut s = String::from("&");
+  for _ in 0..integral {
+    s.push_str(&digit().to_string());
+  }
+  s.push('.');
+  for _ in 0..fractional {
+    s.push_str(&digit().to_string());
+  }
+  ternary(&s)
+}
+
+#[test]
+fn integers() {
+  assert_eq!(DualBalancedTernary::from(BigInt::from(0)), ternary("&5"));
+  assert_eq!(DualBalancedTernary::from(BigInt::from(7)), ternary("&191"));
+  assert_eq!(DualBalancedTernary::from(BigInt::from(-4)), ternary("&99"));
+  assert_eq!(DualBalancedTernary::from((BigInt::from(1), BigInt::from(2))), ternary("&14"));
+  assert_eq!(BigInt::try_from(ternary("&191")), Ok(BigInt::from(7)));
+  assert_eq!(BigInt::try_from(ternary("&3")), Err(DbtError::NonLinearValue));
+  assert_eq!(BigInt::try_from(ternary("&1.1")), Err(DbtError::NonIntegralValue));
+  assert_eq!(
+    <(BigInt, BigInt)>::try_from(ternary("&66")),
+    Ok((BigInt::from(-4), BigInt::from(4)))
+  );
+
+  let big: BigInt = BigInt::from(3u8).pow(200) - 1;
+  assert_eq!(BigInt::try_from(DualBalancedTernary::from(big.to_owned())), Ok(big.to_owned()));
+  assert_eq!(BigInt::try_from(DualBalancedTernary::from(-big.to_owned())), Ok(-big));
+  for n in [-1i64, 1, 2, 3, 4, 13, -13, 1 << 40, i64::MIN, i64::MAX] {
+    assert_eq!(DualBalancedTernary::from(BigInt::from(n)), DualBalancedTernary::from(n));
+  }
+}
+
+#[test]
+fn scaled() {
+  let (x, y, k) = ternary("&.555559285").to_scaled_bigints();
+  assert_eq!((x.to_owned(), y.to_owned(), k), (BigInt::from(-2), BigInt::from(-11), 8));
+  assert_eq!(DualBalancedTernary::from_scaled_bigints(&x, &y, k), ternary("&.555559285"));
+  assert_eq!(ternary("&5").to_scaled_bigints(), (BigInt::from(0), BigInt::from(0), 0));
+
+  let v = long_value(7, 300, 200);
+  let (x, y, k) = v.to_scaled_bigints();
+  assert_eq!(DualBalancedTernary::from_scaled_bigints(&x, &y, k), v);
+}
+
+#[test]
+fn long_multiplication() {
+  // both sides of Karatsuba threshold, and unbalanced lengths
+  for (seed, a_len, b_len) in [(1, 10, 12), (2, 40, 33), (3, 200, 180), (4, 500, 70), (5, 1000, 1000)] {
+    let a = long_value(seed, a_len, a_len / 3);
+    let b = long_value(seed + 100, b_len, b_len / 2);
+    let (ax, ay, ak) = a.to_scaled_bigints();
+    let (bx, by, bk) = b.to_scaled_bigints();
+    // (ay + ax i) (by + bx i)
+    let x = &ay * &bx + &ax * &by;
+    let y = &ay * &by - &ax * &bx;
+    let expected = DualBalancedTernary::from_scaled_bigints(&x, &y, ak + bk);
+    assert_eq!(a * b, expected);
+  }
+}
@@ -2,10 +2,10 @@ extern crate dual_balanced_ternary;
 use core::convert::TryInto;
 use std::convert::TryFrom;
 
-use dual_balanced_ternary::{ternary, DualBalancedTernary, DualBalancedTernaryDigit::*};
+use dual_balanced_ternary::{ternary, DbtError, DualBalancedTernary, DualBalancedTernaryDigit::*};
 
 #[test]
-fn to_buffer() -> Result<(), String> {
+fn to_buffer() -> Result<(), DbtError> {
   assert_eq!(
     DualBalancedTernary::try_from(&TryInto::<Vec<u8>>::try_into(ternary("&."))?),
     Ok(ternary("&."))
@@ -34,14 +34,8 @@ fn to_buffer() -> Result<(), String> {
   );
 
   assert_eq!(
-    TryInto::<Vec<u8>>::try_into(DualBalancedTernary {
-      integral: vec![Dbt1, Dbt5],
-      fractional: vec![Dbt1, Dbt5]
-    }),
-    TryInto::<Vec<u8>>::try_into(DualBalancedTernary {
-      integral: vec![Dbt1],
-      fractional: vec![Dbt1]
-    }),
+    TryInto::<Vec<u8>>::try_into(DualBalancedTernary::from_digits(vec![Dbt1, Dbt5], vec![Dbt1, Dbt5])),
+    TryInto::<Vec<u8>>::try_into(DualBalancedTernary::from_digits(vec![Dbt1], vec![Dbt1])),
   );
 
   Ok(())
@@ -0,0 +1,60 @@
+extern crate dual_balanced_ternary;
+
+use dual_balanced_ternary::{ternary, DbtError, DualBalancedTernary};
+
+#[test]
+fn check_round_trip() {
+  for s in ["&5", "&1", "&.1", "&3445647.674", "&99.11", "&12345678987654321"] {
+    let v = ternary(s);
+    let checked = v.format_with_check();
+    assert!(checked.starts_with(&format!("{}-", s)));
+    assert_eq!(DualBalancedTernary::parse_checked(&checked), Ok(v));
+  }
+}
+
+#[test]
+fn check_rejects_typos() {
+  for s in ["&3445647.674", "&12.9", "&.1852", "&77777"] {
+    let checked = ternary(s).format_with_check();
+    let chars: Vec<char> = checked.chars().collect();
+    let body_len = s.len();
+
+    // every single wrong digit, including the check digit
+    for idx in 1..chars.len() {
+      if chars[idx] == '.' || chars[idx] == '-' {
+        continue;
+      }
+      for d in "123456789".chars().filter(|d| *d != chars[idx]) {
+        let mut typo = chars.clone();
+        typo[idx] = d;
+        let typo: String = typo.into_iter().collect();
+        assert!(DualBalancedTernary::parse_checked(&typo).is_err(), "{} accepted", typo);
+      }
+    }
+
+    // every swap of neighbours in the value, including the radix point
+    for idx in 1..body_len - 1 {
+      if chars[idx] == chars[idx + 1] {
+        continue;
+      }
+      let mut typo = chars.clone();
+      typo.swap(idx, idx + 1);
+      let typo: String = typo.into_iter().collect();
+      assert!(DualBalancedTernary::parse_checked(&typo).is_err(), "{} accepted", typo);
+    }
+  }
+}
+
+#[test]
+fn check_errors() {
+  let checked = ternary("&1.1").format_with_check();
+  assert_eq!(DualBalancedTernary::parse_checked("&1.1"), Err(DbtError::MissingCheckDigit));
+  assert_eq!(DualBalancedTernary::parse_checked("&1.1-"), Err(DbtError::MissingCheckDigit));
+  assert_eq!(
+    DualBalancedTernary::parse_checked(&format!("{}1", checked)),
+    Err(DbtError::MissingCheckDigit)
+  );
+  assert_eq!(DualBalancedTernary::parse_checked("1.1-1"), Err(DbtError::MissingPrefix));
+  assert_eq!(DualBalancedTernary::parse_checked("&1.1-0"), Err(DbtError::CheckDigitMismatch));
+  assert_eq!(DbtError::CheckDigitMismatch.to_string(), "check digit does not match");
+}
@@ -0,0 +1,133 @@
+extern crate dual_balanced_ternary;
+
+use std::convert::TryInto;
+
+use dual_balanced_ternary::codec::{decode, decode_packed, decode_prefix, decode_strict, encode_nibbles, encode_packed, PACKED_MAGIC};
+use dual_balanced_ternary::{ternary, DbtError, DualBalancedTernary};
+
+#[test]
+fn packed_round_trip() {
+  let digits = "123456789";
+  // every length of both parts modulo a group of 5
+  for int_len in 0..12 {
+    for frac_len in 0..12 {
+      let integral: String = digits.chars().cycle().skip(int_len).take(int_len).collect();
+      let fractional: String = digits.chars().cycle().skip(frac_len + 3).take(frac_len).collect();
+      let v = ternary(&format!("&{}.{}", integral, fractional));
+      assert_eq!(decode_packed(&encode_packed(&v)), Ok(v));
+    }
+  }
+  assert_eq!(
+    decode_packed(&encode_packed(&ternary("&5553445647.674555"))),
+    Ok(ternary("&3445647.674"))
+  );
+}
+
+#[test]
+fn packed_layout() {
+  assert_eq!(encode_packed(&ternary("&5")), vec![0xDB, 0xFE, 0, 0]);
+  // digits 1,1 are 0,0 then padded with `5` as 4, 0 + 4 * (81 + 729 + 6561) = 0x732c
+  assert_eq!(encode_packed(&ternary("&1.1")), vec![0xDB, 0xFE, 1, 1, 0x2c, 0x73]);
+  assert_eq!(encode_packed(&ternary("&9")), vec![0xDB, 0xFE, 1, 0, 0x58, 0x73]);
+
+  // smaller than legacy nibbles once the header is paid off
+  let v = ternary(&format!("&{}.{}", "3445647364".repeat(4), "67443".repeat(4)));
+  let legacy: Vec<u8> = v.to_owned().try_into().unwrap();
+  assert!(encode_packed(&v).len() < legacy.len());
+
+  let long = DualBalancedTernary::from(i128::MAX) * DualBalancedTernary::from(i128::MAX);
+  let buf = encode_packed(&long);
+  // 161 integral digits, varint takes 2 bytes
+  assert_eq!(buf[2..4], [0xa1, 0x01]);
+  assert_eq!(decode_packed(&buf), Ok(long));
+}
+
+#[test]
+fn packed_errors() {
+  assert_eq!(decode_packed(&[]), Err(DbtError::BufferTooShort));
+  assert_eq!(decode_packed(&[1, 21, 21]), Err(DbtError::UnknownFormat));
+  assert_eq!(decode_packed(&[0xDB, 0xFE, 1]), Err(DbtError::BufferTooShort));
+  assert_eq!(decode_packed(&[0xDB, 0xFE, 1, 1, 0x40]), Err(DbtError::BufferTooShort));
+  assert_eq!(
+    decode_packed(&[0xDB, 0xFE, 1, 0, 0xff, 0xff]),
+    Err(DbtError::InvalidPackedGroup(0xffff))
+  );
+  let mut huge = PACKED_MAGIC.to_vec();
+  huge.extend_from_slice(&[0xff; 11]);
+  huge.push(0x01);
+  assert_eq!(decode_packed(&huge), Err(DbtError::InvalidVarint(2)));
+}
+
+#[test]
+fn nibble_round_trip() {
+  let digits = "123456789";
+  for int_len in 0..6 {
+    for frac_len in 0..6 {
+      let integral: String = digits.chars().cycle().skip(int_len).take(int_len).collect();
+      let fractional: String = digits.chars().cycle().skip(frac_len + 3).take(frac_len).collect();
+      let v = ternary(&format!("&{}.{}", integral, fractional));
+      assert_eq!(decode(&encode_nibbles(&v)), Ok(v));
+    }
+  }
+
+  assert_eq!(encode_nibbles(&ternary("&5")), vec![0xDB, 0x01, 0, 0]);
+  assert_eq!(encode_nibbles(&ternary("&12.3")), vec![0xDB, 0x01, 2, 1, 0x21, 0x35]);
+  // a real `5` digit is kept apart from padding by lengths
+  assert_eq!(encode_nibbles(&ternary("&15.1")), vec![0xDB, 0x01, 2, 1, 0x51, 0x15]);
+  assert_eq!(decode(&[0xDB, 0x01, 2, 1, 0x51, 0x15]), Ok(ternary("&15.1")));
+
+  let legacy: Vec<u8> = ternary("&12.3").try_into().unwrap();
+  assert_eq!(legacy, encode_nibbles(&ternary("&12.3")));
+}
+
+#[test]
+fn decode_every_format() {
+  let v = ternary("&3445647.674");
+  assert_eq!(decode(&encode_nibbles(&v)), Ok(v.to_owned()));
+  assert_eq!(decode(&encode_packed(&v)), Ok(v.to_owned()));
+  // legacy layout, integral length then pairs from unit position, each part padded
+  assert_eq!(decode(&[7, 0x74, 0x65, 0x44, 0x35, 0x67, 0x45]), Ok(v));
+  assert_eq!(decode(&[1, 21, 21]), Ok(ternary("&1.1")));
+  assert_eq!(decode(&[255, 0x11]), Err(DbtError::BufferTooShort));
+
+  // 219 integral digits make a legacy buffer start with `0xDB`, the second byte tells it apart
+  let mut legacy = vec![0xDB];
+  legacy.extend_from_slice(&[0x11; 110]);
+  assert_eq!(decode(&legacy), Ok(ternary(&format!("&{}", "1".repeat(220)))));
+  assert_eq!(decode_prefix(&legacy), Err(DbtError::UnknownFormat));
+  assert!(matches!(decode_strict(&legacy), Err(DbtError::NonCanonical { .. })));
+}
+
+#[test]
+fn strict_decoding() {
+  for s in ["&5", "&1.1", "&3445647.674", "&.9"] {
+    let v = ternary(s);
+    assert_eq!(decode_strict(&encode_nibbles(&v)), Ok(v.to_owned()));
+    assert_eq!(decode_strict(&encode_packed(&v)), Ok(v));
+  }
+
+  let non_canonical = |offset| Err(DbtError::NonCanonical { offset });
+  // `&12.3`, canonical bytes are [0xDB, 0x01, 2, 1, 0x21, 0x35]
+  assert_eq!(decode_strict(&[0xDB, 0x01, 2, 1, 0x21, 0x35, 0]), non_canonical(6));
+  assert_eq!(decode_strict(&[0xDB, 0x01, 2, 1, 0x21, 0x31]), non_canonical(5));
+  assert_eq!(decode_strict(&[0xDB, 0x01, 0x82, 0, 1, 0x21, 0x35]), non_canonical(2));
+  // `5` at ends of each part
+  assert_eq!(decode_strict(&[0xDB, 0x01, 3, 1, 0x21, 0x53, 0x55]), non_canonical(2));
+  assert_eq!(decode_strict(&[0xDB, 0x01, 2, 2, 0x21, 0x35]), non_canonical(3));
+
+  assert_eq!(decode_strict(&[]), non_canonical(0));
+  assert_eq!(decode_strict(&[1, 21, 21]), non_canonical(0));
+  assert_eq!(decode_strict(&[0xDB]), non_canonical(1));
+  assert_eq!(decode_strict(&[0xDB, 0x02, 0, 0]), non_canonical(1));
+  assert_eq!(decode_strict(&[0xDB, 0x01, 2]), non_canonical(3));
+  assert_eq!(decode_strict(&[0xDB, 0x01, 4, 0, 0x21, 0x30]), non_canonical(5));
+  assert_eq!(decode_strict(&[0xDB, 0x01, 4, 0, 0x21]), non_canonical(5));
+  assert_eq!(decode_strict(&[0xDB, 0xFE, 6, 0, 0, 0, 0xff, 0xff]), non_canonical(6));
+  let mut huge = vec![0xDB, 0x01];
+  huge.extend_from_slice(&[0xff; 11]);
+  huge.push(0x01);
+  assert_eq!(decode_strict(&huge), non_canonical(2));
+
+  // lenient decoding takes them all
+  assert_eq!(decode(&[0xDB, 0x01, 3, 1, 0x21, 0x53, 0x55]), Ok(ternary("&12.3")));
+}
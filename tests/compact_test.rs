@@ -0,0 +1,88 @@
+extern crate dual_balanced_ternary;
+
+use dual_balanced_ternary::{ternary, DbtError, DualBalancedTernary};
+
+#[test]
+fn compact_round_trip() {
+  assert_eq!(ternary("&5").to_compact_string(), "e");
+  assert_eq!(ternary("&1").to_compact_string(), "a");
+  assert_eq!(ternary("&11").to_compact_string(), "0");
+  assert_eq!(ternary("&.1").to_compact_string(), ".4");
+  assert_eq!(ternary("&99.99").to_compact_string(), "~G.~G");
+  assert_eq!(ternary("&.8").to_compact_string(), ".~3");
+  assert_eq!(ternary("&3445647.674").to_compact_string(), "cUfX.pV");
+
+  let digits = "123456789";
+  for int_len in 0..5 {
+    for frac_len in 0..5 {
+      let integral: String = digits.chars().cycle().skip(int_len * 2).take(int_len).collect();
+      let fractional: String = digits.chars().cycle().skip(frac_len + 1).take(frac_len).collect();
+      let s = format!("&{}.{}", integral, fractional);
+      let v = ternary(&s);
+      let compact = v.to_compact_string();
+      assert!(compact.len() <= s.len(), "{} is longer than {}", compact, s);
+      assert_eq!(DualBalancedTernary::from_compact_str(&compact), Ok(v), "{}", compact);
+    }
+  }
+}
+
+#[test]
+fn compact_alphabet() {
+  // every pair of digits gets its own text, of unreserved characters in RFC 3986
+  let unreserved = |c: char| c.is_ascii_alphanumeric() || "-._~".contains(c);
+  let mut seen = std::collections::HashSet::new();
+  for a in "123456789".chars() {
+    for b in "123456789".chars() {
+      let v = ternary(&format!("&{}{}.{}{}", a, b, b, a));
+      let s = v.to_compact_string();
+      assert!(s.chars().all(unreserved), "{}", s);
+      assert_eq!(DualBalancedTernary::from_compact_str(&s), Ok(v.to_owned()), "{}", s);
+      seen.insert(ternary(&format!("&{}{}", a, b)).to_compact_string());
+    }
+  }
+  assert_eq!(seen.len(), 81);
+}
+
+#[test]
+fn compact_url_safe() {
+  // only unreserved characters of RFC 3986, for every 4 digits split at every position
+  let unreserved = |c: char| c.is_ascii_alphanumeric() || "-._~".contains(c);
+  let digits: Vec<char> = "123456789".chars().collect();
+  for n in 0..9 * 9 * 9 * 9 {
+    let s: String = (0..4).map(|i| digits[n / 9usize.pow(i) % 9]).collect();
+    for split in 0..=4 {
+      let text = format!("&{}.{}", &s[..split], &s[split..]);
+      let v = ternary(&text);
+      let compact = v.to_compact_string();
+      assert!(compact.chars().all(unreserved), "{} for {}", compact, text);
+      assert!(compact.len() <= text.len(), "{} is longer than {}", compact, text);
+      assert_eq!(DualBalancedTernary::from_compact_str(&compact), Ok(v), "{}", compact);
+    }
+  }
+  // the worst case, every pair escaped
+  assert_eq!(ternary("&9999.99").to_compact_string(), "~G~G.~G");
+}
+
+#[test]
+fn compact_errors() {
+  assert_eq!(DualBalancedTernary::from_compact_str(""), Err(DbtError::EmptyNumber));
+  assert_eq!(DualBalancedTernary::from_compact_str("e.4.4"), Err(DbtError::TooManyRadixPoints));
+  assert_eq!(
+    DualBalancedTernary::from_compact_str("ab&"),
+    Err(DbtError::InvalidDigit { ch: '&', position: 2 })
+  );
+  assert_eq!(
+    DualBalancedTernary::from_compact_str("a~"),
+    Err(DbtError::InvalidDigit { ch: '~', position: 1 })
+  );
+  assert_eq!(
+    DualBalancedTernary::from_compact_str("~H"),
+    Err(DbtError::InvalidDigit { ch: 'H', position: 1 })
+  );
+  assert_eq!(
+    DualBalancedTernary::from_compact_str("~.4"),
+    Err(DbtError::InvalidDigit { ch: '.', position: 1 })
+  );
+  assert_eq!(DualBalancedTernary::from_compact_str("e.e"), Ok(ternary("&5")));
+  assert_eq!(DualBalancedTernary::from_compact_str(".4"), Ok(ternary("&.1")));
+}
@@ -0,0 +1,69 @@
+extern crate dual_balanced_ternary;
+
+use dual_balanced_ternary::{ternary, ArithmeticContext, DbtError, DualBalancedTernary, RoundingMode};
+
+#[test]
+fn divide_with_precision() {
+  let ctx = ArithmeticContext::default();
+  assert_eq!(ternary("&111").div_with(&ternary("&23"), &ctx), Ok(ternary("&47")));
+  assert_eq!(ternary("&3.49").div_with(&ternary("&3.3"), &ctx), Ok(ternary("&1.3")));
+  assert_eq!(ternary("&111").div_with(&ternary("&5"), &ctx), Err(DbtError::DivisionByZero));
+
+  // 1/2 is &.1111... in balanced ternary
+  assert_eq!(
+    ternary("&1").div_with(&ternary("&19"), &ArithmeticContext::with_precision(4)),
+    Ok(ternary("&.1111"))
+  );
+  assert_eq!(
+    ternary("&1").div_with(&ternary("&19"), &ArithmeticContext::with_precision(7)),
+    Ok(ternary("&.1111111"))
+  );
+  assert_eq!(
+    ternary("&743317").div_with(&ternary("&616"), &ArithmeticContext::with_precision(4)),
+    Ok(ternary("&751.5529"))
+  );
+}
+
+#[test]
+fn divide_with_rounding() {
+  let floor = ArithmeticContext::new(4, RoundingMode::Floor);
+  let ceil = ArithmeticContext::new(4, RoundingMode::Ceil);
+  assert_eq!(ternary("&1").div_with(&ternary("&19"), &floor), Ok(ternary("&.1111")));
+  assert_eq!(ternary("&1").div_with(&ternary("&19"), &ceil), Ok(ternary("&1.9999")));
+  assert_eq!(ternary("&3").div_with(&ternary("&19"), &ceil), Ok(ternary("&3.7777")));
+
+  // rounded separately on each axis
+  assert_eq!(ternary("&743317").div_with(&ternary("&616"), &floor), Ok(ternary("&751.5522")));
+  assert_eq!(ternary("&743317").div_with(&ternary("&616"), &ceil), Ok(ternary("&751.552")));
+
+  // exact results are not touched
+  assert_eq!(ternary("&743316").div_with(&ternary("&616"), &ceil), Ok(ternary("&751")));
+}
+
+#[test]
+fn multiply_with_precision() {
+  let a = ternary("&.5536");
+  let b = ternary("&.5543");
+  assert_eq!(a.mul_with(&b, &ArithmeticContext::with_precision(8)), ternary("&.55555928"));
+  assert_eq!(a.mul_with(&b, &ArithmeticContext::with_precision(5)), ternary("&5"));
+  assert_eq!(a.mul_with(&b, &ArithmeticContext::new(5, RoundingMode::Floor)), ternary("&.55552"));
+  assert_eq!(a.mul_with(&b, &ArithmeticContext::new(5, RoundingMode::Ceil)), ternary("&5"));
+  assert_eq!(
+    ternary("&3.3").mul_with(&ternary("&1.3"), &ArithmeticContext::with_precision(1)),
+    ternary("&3.4")
+  );
+}
+
+#[test]
+fn float_with_precision() {
+  let at =
+    |precision: usize, rounding: RoundingMode| DualBalancedTernary::from_f64_with(0.5, &ArithmeticContext::new(precision, rounding));
+  assert_eq!(at(2, RoundingMode::Nearest), Ok(ternary("&.33")));
+  assert_eq!(at(2, RoundingMode::Floor), Ok(ternary("&.33")));
+  assert_eq!(at(2, RoundingMode::Ceil), Ok(ternary("&3.77")));
+  assert_eq!(at(6, RoundingMode::Nearest), Ok(ternary("&.333333")));
+
+  let ctx = ArithmeticContext::default();
+  assert_eq!(DualBalancedTernary::from_f64_with(4.0, &ctx), Ok(ternary("&33")));
+  assert_eq!(DualBalancedTernary::from_f64_with(f64::NAN, &ctx), Err(DbtError::NonFiniteFloat));
+}
@@ -0,0 +1,71 @@
+extern crate dual_balanced_ternary;
+
+use std::convert::TryFrom;
+
+use dual_balanced_ternary::{ternary, DbtError, DualBalancedTernary};
+
+#[test]
+fn from_integers() {
+  assert_eq!(DualBalancedTernary::from(0), ternary("&5"));
+  assert_eq!(DualBalancedTernary::from(1i8), ternary("&1"));
+  assert_eq!(DualBalancedTernary::from(-1i16), ternary("&9"));
+  assert_eq!(DualBalancedTernary::from(2u8), ternary("&19"));
+  assert_eq!(DualBalancedTernary::from(7i32), ternary("&191"));
+  assert_eq!(DualBalancedTernary::from((1i64, 2i64)), DualBalancedTernary::new(1.0, 2.0));
+  assert_eq!(DualBalancedTernary::from((1i64, 2i64)), ternary("&14"));
+  assert_eq!(DualBalancedTernary::from((-3i32, 0i32)), ternary("&75"));
+
+  // floats lose these
+  let big = 9_007_199_254_740_993i64;
+  assert_eq!(i64::try_from(DualBalancedTernary::from(big)), Ok(big));
+}
+
+#[test]
+fn round_trip() {
+  for n in [i128::MIN, i128::MIN + 1, -1, 0, 1, i128::MAX] {
+    assert_eq!(i128::try_from(DualBalancedTernary::from(n)), Ok(n));
+  }
+  for n in [0, 1, u128::MAX - 1, u128::MAX] {
+    assert_eq!(u128::try_from(DualBalancedTernary::from(n)), Ok(n));
+  }
+  for n in [i64::MIN, -40, 40, i64::MAX] {
+    assert_eq!(i64::try_from(DualBalancedTernary::from(n)), Ok(n));
+    assert_eq!(<(i64, i64)>::try_from(DualBalancedTernary::from((n, n / -2))), Ok((n, n / -2)));
+  }
+  assert_eq!(u8::try_from(DualBalancedTernary::from(255u8)), Ok(255));
+  assert_eq!(<(usize, usize)>::try_from(ternary("&8")), Ok((1, 1)));
+}
+
+#[test]
+fn conversion_failures() {
+  assert_eq!(i64::try_from(ternary("&1.1")), Err(DbtError::NonIntegralValue));
+  assert_eq!(i64::try_from(ternary("&3")), Err(DbtError::NonLinearValue));
+  assert_eq!(u8::try_from(DualBalancedTernary::from(256)), Err(DbtError::IntegerOverflow));
+  assert_eq!(i8::try_from(DualBalancedTernary::from(-129)), Err(DbtError::IntegerOverflow));
+  assert_eq!(u32::try_from(ternary("&9")), Err(DbtError::IntegerOverflow));
+  assert_eq!(<(u8, u8)>::try_from(ternary("&2")), Err(DbtError::IntegerOverflow));
+  assert_eq!(i128::try_from(DualBalancedTernary::from(u128::MAX)), Err(DbtError::IntegerOverflow));
+  let huge = DualBalancedTernary::from(u128::MAX) * ternary("&11");
+  assert_eq!(u128::try_from(huge), Err(DbtError::IntegerOverflow));
+}
+
+#[test]
+fn scaled_integers() {
+  assert_eq!(ternary("&1.1").to_scaled_integers(), Ok((0, 4, 1)));
+  assert_eq!(ternary("&14").to_scaled_integers(), Ok((1, 2, 0)));
+  assert_eq!(ternary("&.3").to_scaled_integers(), Ok((1, 0, 1)));
+  assert_eq!(ternary("&.555559285").to_scaled_integers(), Ok((-2, -11, 8)));
+  assert_eq!(ternary("&5").to_scaled_integers(), Ok((0, 0, 0)));
+
+  assert_eq!(DualBalancedTernary::from_scaled_integers(0, 4, 1), ternary("&1.1"));
+  assert_eq!(DualBalancedTernary::from_scaled_integers(3, 0, 1), ternary("&3"));
+  assert_eq!(DualBalancedTernary::from_scaled_integers(0, 0, 4), ternary("&5"));
+
+  for s in ["&.55555928", "&12.34", "&9.41658555559", "&743317.616"] {
+    let (x, y, k) = ternary(s).to_scaled_integers().unwrap();
+    assert_eq!(DualBalancedTernary::from_scaled_integers(x, y, k), ternary(s));
+  }
+
+  let huge = DualBalancedTernary::from(i128::MAX) * ternary("&11");
+  assert_eq!(huge.to_scaled_integers(), Err(DbtError::IntegerOverflow));
+}
@@ -1,6 +1,9 @@
 extern crate dual_balanced_ternary;
 
-use dual_balanced_ternary::{dbt_digits, ternary, DualBalancedTernaryDigit::*};
+use std::convert::TryFrom;
+
+use dual_balanced_ternary::complex::ComplexXy;
+use dual_balanced_ternary::{dbt_digits, ternary, DualBalancedTernaryDigit, DualBalancedTernaryDigit::*};
 
 #[test]
 fn equality() {
@@ -9,3 +12,27 @@ fn equality() {
     vec![(1, Dbt2), (0, Dbt3), (-1, Dbt4), (-2, Dbt5), (-3, Dbt6)],
   )
 }
+
+#[test]
+fn multiply_digits() {
+  let digits = [Dbt1, Dbt2, Dbt3, Dbt4, Dbt5, Dbt6, Dbt7, Dbt8, Dbt9];
+  // `1` is the identity
+  for d in digits {
+    assert_eq!(Dbt1 * d, (Dbt5, d));
+    assert_eq!(d * Dbt1, (Dbt5, d));
+  }
+  for a in digits {
+    for b in digits {
+      assert_eq!(a * b, b * a);
+    }
+  }
+}
+
+#[test]
+fn digit_coordinates() {
+  let digits = [Dbt1, Dbt2, Dbt3, Dbt4, Dbt5, Dbt6, Dbt7, Dbt8, Dbt9];
+  for d in digits {
+    let c: ComplexXy = d.into();
+    assert_eq!(DualBalancedTernaryDigit::try_from((c.x as i64, c.y as i64)), Ok(d));
+  }
+}
@@ -0,0 +1,82 @@
+extern crate dual_balanced_ternary;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use dual_balanced_ternary::{try_ternary, DbtError, DualBalancedTernary, DualBalancedTernaryDigit};
+
+#[test]
+fn parse_errors() {
+  assert_eq!(try_ternary(""), Err(DbtError::MissingPrefix));
+  assert_eq!(try_ternary("1.1"), Err(DbtError::MissingPrefix));
+  assert_eq!(try_ternary("&"), Err(DbtError::EmptyNumber));
+  assert_eq!(try_ternary("&1.1.1"), Err(DbtError::TooManyRadixPoints));
+  assert_eq!(try_ternary("&12a.4"), Err(DbtError::InvalidDigit { ch: 'a', position: 3 }));
+  assert_eq!(try_ternary("&1.40"), Err(DbtError::InvalidDigit { ch: '0', position: 4 }));
+  // positions count characters, not bytes
+  assert_eq!(try_ternary("&1é2"), Err(DbtError::InvalidDigit { ch: 'é', position: 2 }));
+  assert_eq!(try_ternary("&1.1→x"), Err(DbtError::InvalidDigit { ch: '→', position: 4 }));
+  assert_eq!(DualBalancedTernary::from_str("&1.1"), try_ternary("&1.1"));
+}
+
+#[test]
+fn conversion_errors() {
+  assert_eq!(DualBalancedTernaryDigit::try_from(0u8), Err(DbtError::InvalidDigitValue(0)));
+  assert_eq!(DualBalancedTernaryDigit::try_from('x'), Err(DbtError::InvalidDigitChar('x')));
+  assert_eq!(
+    DualBalancedTernaryDigit::try_from((2, 0)),
+    Err(DbtError::InvalidCoordinate { x: 2, y: 0 })
+  );
+
+  assert_eq!(DualBalancedTernary::try_from(f64::NAN), Err(DbtError::NonFiniteFloat));
+  assert_eq!(DualBalancedTernary::try_new(1.0, f64::INFINITY), Err(DbtError::NonFiniteFloat));
+  assert_eq!(DualBalancedTernary::try_new(4.0, 6.0), try_ternary("&143"));
+
+  // integral parts go through `i64`
+  assert_eq!(DualBalancedTernary::try_from(9.3e18), Err(DbtError::FloatOutOfRange));
+  assert_eq!(DualBalancedTernary::try_from(-(2f64.powi(63))), Err(DbtError::FloatOutOfRange));
+  assert_eq!(DualBalancedTernary::try_new(1.0, 1e300), Err(DbtError::FloatOutOfRange));
+  assert_eq!(
+    DualBalancedTernary::try_from(2f64.powi(62)),
+    Ok(DualBalancedTernary::from_scaled_integers(1 << 62, 0, 0))
+  );
+}
+
+#[test]
+fn tiny_negative_floats() {
+  // `x - x.floor()` rounds to `1.0` for these
+  for x in [-1e-20, -f64::MIN_POSITIVE, -f64::EPSILON / 4.0] {
+    assert_eq!(DualBalancedTernary::try_from(x), try_ternary("&5"));
+    assert_eq!(DualBalancedTernary::try_new(x, 1.0), try_ternary("&1"));
+    assert_eq!(DualBalancedTernary::try_new(2.0, x), try_ternary("&37"));
+  }
+  assert_eq!(DualBalancedTernary::try_from(-1.0 - 1e-20), try_ternary("&7"));
+}
+
+#[test]
+fn buffer_errors() {
+  assert_eq!(DualBalancedTernary::try_from(&vec![]), Err(DbtError::BufferTooShort));
+  assert_eq!(DualBalancedTernary::try_from(&vec![4, 0x11]), Err(DbtError::BufferTooShort));
+  assert_eq!(DualBalancedTernary::try_from(&vec![1, 0x10]), Err(DbtError::InvalidDigitValue(0)));
+
+  // legacy header used to overflow at 255
+  assert_eq!(DualBalancedTernary::try_from(&vec![255, 0x11]), Err(DbtError::BufferTooShort));
+  assert_eq!(DualBalancedTernary::try_from(&vec![0xDB, 0x01, 1]), Err(DbtError::BufferTooShort));
+  assert_eq!(
+    DualBalancedTernary::try_from(&vec![0xDB, 0x01, 1, 0, 0xa5]),
+    Err(DbtError::InvalidDigitValue(10))
+  );
+
+  // lengths are varints now
+  let long = DualBalancedTernary::from_str(&format!("&{}", "1".repeat(256))).unwrap();
+  assert_eq!(
+    DualBalancedTernary::try_from(&Vec::<u8>::try_from(long.to_owned()).unwrap()),
+    Ok(long)
+  );
+}
+
+#[test]
+fn error_messages() {
+  let e: Box<dyn std::error::Error> = Box::new(DbtError::MissingPrefix);
+  assert_eq!(e.to_string(), "ternary requires & symbol");
+  assert_eq!(DbtError::IntegralTooLong(300).to_string(), "integral part too long: 300");
+}
@@ -0,0 +1,78 @@
+extern crate dual_balanced_ternary;
+
+use std::convert::TryFrom;
+
+use dual_balanced_ternary::{ternary, DbtError, DualBalancedTernary, FixedDbt};
+
+type Small = FixedDbt<2, 2>;
+
+fn fixed(s: &str) -> Small {
+  Small::try_from(ternary(s)).unwrap()
+}
+
+#[test]
+fn convert() {
+  assert_eq!(DualBalancedTernary::from(fixed("&12.34")), ternary("&12.34"));
+  assert_eq!(DualBalancedTernary::from(fixed("&5")), ternary("&5"));
+  assert_eq!(format!("{}", fixed("&1.1")), "&1.1");
+  assert_eq!(fixed("&5"), Small::ZERO);
+  assert_eq!(Small::default(), Small::ZERO);
+  assert!(Small::ZERO.is_zero());
+
+  // cut off at `FRAC` digits
+  assert_eq!(fixed("&1.1111"), fixed("&1.11"));
+  assert_eq!(Small::try_from(ternary("&111")), Err(DbtError::IntegralTooLong(3)));
+  assert_eq!(Small::try_from(ternary("&511")), Ok(fixed("&11")));
+}
+
+#[test]
+fn arithmetic() {
+  assert_eq!(fixed("&1") + fixed("&1"), fixed("&19"));
+  assert_eq!(fixed("&1") + fixed("&3"), fixed("&8"));
+  assert_eq!(fixed("&19") - fixed("&1"), fixed("&1"));
+  assert_eq!(fixed("&3") * fixed("&3"), fixed("&9"));
+  assert_eq!(fixed("&.1") * fixed("&.1"), fixed("&.51"));
+  assert_eq!(-fixed("&12.34"), fixed("&98.76"));
+
+  // same digits as the dynamic type, while `FRAC` is enough
+  let values = ["&1.1", "&23.4", "&7.26", "&86", "&9.9"];
+  for a in values {
+    for b in values {
+      let sum = ternary(a) + ternary(b);
+      if let Ok(expected) = Small::try_from(sum) {
+        assert_eq!(fixed(a).checked_add(&fixed(b)), Some(expected));
+      }
+      let product = (ternary(a) * ternary(b)).round_n(2);
+      if let Ok(expected) = Small::try_from(product.to_owned()) {
+        assert_eq!(fixed(a).checked_mul(&fixed(b)), Some(expected), "{} * {}", a, b);
+      }
+    }
+  }
+}
+
+#[test]
+fn overflow() {
+  // 4 + 4 on `1` direction, out of -4..=4, wraps to -1
+  let four = fixed("&11");
+  assert_eq!(four.checked_add(&four), None);
+  assert_eq!(four.wrapping_add(&four), fixed("&9"));
+  assert_eq!(four.saturating_add(&four), fixed("&11.11"));
+  assert_eq!(four.checked_mul(&fixed("&19")), None);
+  assert_eq!((-four).saturating_sub(&four), fixed("&99.99"));
+
+  // only `1` direction overflows, `3` direction is kept
+  let v = fixed("&18");
+  assert_eq!(v.saturating_add(&four), fixed("&18.11"));
+  assert_eq!(v.wrapping_add(&four), fixed("&4"));
+  assert_eq!(v.checked_sub(&four), Some(fixed("&3")));
+
+  // wrapping is `mod 9` on each direction
+  let w = fixed("&1").wrapping_mul(&fixed("&11")).wrapping_mul(&fixed("&19"));
+  assert_eq!(DualBalancedTernary::from(w), ternary("&9"));
+}
+
+#[test]
+#[should_panic]
+fn add_overflow_panics() {
+  let _ = fixed("&11") + fixed("&1");
+}
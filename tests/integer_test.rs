@@ -0,0 +1,97 @@
+extern crate dual_balanced_ternary;
+
+use dual_balanced_ternary::complex::ComplexXy;
+use dual_balanced_ternary::{ternary, DbtError, DualBalancedTernary};
+
+fn norm(x: &DualBalancedTernary) -> f64 {
+  let c = ComplexXy::from(x.to_owned());
+  c.x * c.x + c.y * c.y
+}
+
+#[test]
+fn test_div_rem() {
+  assert_eq!(ternary("&111").div_rem(&ternary("&23")), (ternary("&47"), ternary("&5")));
+  assert_eq!(ternary("&743317").div_rem(&ternary("&616")), (ternary("&751"), ternary("&9")));
+  // 7 = 2 * 3 + 1, on `1` direction
+  assert_eq!(ternary("&191").div_rem(&ternary("&15")), (ternary("&19"), ternary("&1")));
+  assert_eq!(ternary("&191") % ternary("&15"), ternary("&1"));
+
+  assert_eq!(ternary("&1").try_div_rem(&ternary("&5")), Err(DbtError::DivisionByZero));
+  assert_eq!(ternary("&1").checked_div_rem(&ternary("&5")), None);
+  assert_eq!(ternary("&5").checked_div_rem(&ternary("&4")), Some((ternary("&5"), ternary("&5"))));
+}
+
+#[test]
+fn remainder_is_smaller() {
+  let divisors = [(1.0, 1.0), (3.0, -2.0), (-4.0, 7.0), (0.0, 5.0), (-6.0, -6.0)];
+  for x in -9..9 {
+    for y in -9..9 {
+      let a = DualBalancedTernary::new(x as f64 * 3.0, y as f64 * 5.0);
+      for (bx, by) in divisors {
+        let b = DualBalancedTernary::new(bx, by);
+        let (q, r) = a.div_rem(&b);
+        assert!(q.is_integral());
+        assert_eq!(q.to_owned() * b.to_owned() + r.to_owned(), a);
+        assert!(norm(&r) * 2.0 <= norm(&b), "{} % {} gives {}", a, b, r);
+      }
+    }
+  }
+}
+
+#[test]
+#[should_panic]
+fn remainder_by_zero() {
+  let _ = ternary("&1") % ternary("&5");
+}
+
+#[test]
+fn test_gcd() {
+  assert_eq!(
+    DualBalancedTernary::new(-3.0, 0.0).normalize_associate(),
+    DualBalancedTernary::new(0.0, 3.0)
+  );
+  assert_eq!(
+    DualBalancedTernary::new(2.0, -1.0).normalize_associate(),
+    DualBalancedTernary::new(1.0, 2.0)
+  );
+  assert_eq!(ternary("&5").normalize_associate(), ternary("&5"));
+
+  let a = DualBalancedTernary::new(0.0, 6.0);
+  let b = DualBalancedTernary::new(0.0, -4.0);
+  assert_eq!(a.gcd(&b), DualBalancedTernary::new(0.0, 2.0));
+  assert_eq!(a.lcm(&b), DualBalancedTernary::new(0.0, 12.0));
+  assert_eq!(a.gcd(&ternary("&5")), a);
+  assert_eq!(a.lcm(&ternary("&5")), ternary("&5"));
+  // 5 = (2 + i)(2 - i), and 2 - i is an associate of 1 + 2i
+  assert_eq!(ternary("&7").gcd(&DualBalancedTernary::new(1.0, 2.0)), ternary("&1"));
+  assert_eq!(
+    DualBalancedTernary::new(0.0, 5.0).gcd(&DualBalancedTernary::new(-1.0, 2.0)),
+    DualBalancedTernary::new(2.0, 1.0)
+  );
+}
+
+#[test]
+fn test_extended_gcd() {
+  let pairs = [
+    ((3.0, 2.0), (-4.0, 1.0)),
+    ((0.0, 6.0), (0.0, 4.0)),
+    ((7.0, -5.0), (0.0, 0.0)),
+    ((-9.0, 6.0), (3.0, 3.0)),
+  ];
+  for ((ax, ay), (bx, by)) in pairs {
+    let a = DualBalancedTernary::new(ax, ay);
+    let b = DualBalancedTernary::new(bx, by);
+    let (g, s, t) = a.extended_gcd(&b);
+    assert_eq!(g, a.gcd(&b));
+    assert_eq!(a * s + b * t, g);
+  }
+
+  // a * x + b * y = c is solvable when gcd divides c
+  let a = DualBalancedTernary::new(0.0, 6.0);
+  let b = DualBalancedTernary::new(3.0, 3.0);
+  let c = DualBalancedTernary::new(6.0, 0.0);
+  let (g, s, t) = a.extended_gcd(&b);
+  let (k, r) = c.div_rem(&g);
+  assert!(r.is_zero());
+  assert_eq!(a * s * k.to_owned() + b * t * k, c);
+}
@@ -1,7 +1,9 @@
 extern crate dual_balanced_ternary;
 
+use std::convert::TryFrom;
+
 use dual_balanced_ternary::complex::ComplexXy;
-use dual_balanced_ternary::{ternary, DualBalancedTernary, DualBalancedTernaryDigit::*};
+use dual_balanced_ternary::{ternary, DbtError, DualBalancedTernary, DualBalancedTernaryDigit::*, ONE, ZERO};
 
 #[test]
 fn equality() {
@@ -13,6 +15,8 @@ fn equality() {
   assert_eq!(ternary("&."), ternary("&.5"));
   assert_eq!(ternary("&."), ternary("&5."));
   assert_eq!(ternary("&."), ternary("&5.5"));
+  assert_eq!(ZERO, ternary("&5"));
+  assert_eq!(*ONE, ternary("&1"));
 }
 
 #[test]
@@ -54,6 +58,27 @@ fn test_add_at() {
   assert_eq!(ternary("&19.9").add_at(0, Dbt1), ternary("&15.9"));
 }
 
+#[test]
+fn canonical_digits() {
+  let v = DualBalancedTernary::from_digits(vec![Dbt1, Dbt5, Dbt5], vec![Dbt5, Dbt9, Dbt5]);
+  assert_eq!(v.integral(), &[Dbt1]);
+  assert_eq!(v.fractional(), &[Dbt5, Dbt9]);
+  assert_eq!(v.into_digits(), (vec![Dbt1], vec![Dbt5, Dbt9]));
+  assert!(DualBalancedTernary::from_digits(vec![Dbt5], vec![Dbt5]).integral().is_empty());
+
+  // results drop `5`s left by carries and cancellation
+  assert!((ternary("&1.1") - ternary("&1.1")).integral().is_empty());
+  assert_eq!((ternary("&11") + ternary("&95")).integral(), &[Dbt1]);
+  assert_eq!(ternary("&1.1").add_at(-1, Dbt9).fractional(), &[] as &[_]);
+  assert_eq!(ternary("&1").move_by(-2).fractional(), &[Dbt5, Dbt1]);
+  assert_eq!(ternary("&.1").move_by(2).integral(), &[Dbt5, Dbt1]);
+  assert_eq!(ternary("&1.59").round_n(1).fractional(), &[] as &[_]);
+
+  let mut set = std::collections::HashSet::new();
+  set.insert(ternary("&1.1"));
+  assert!(set.contains(&DualBalancedTernary::from_digits(vec![Dbt1, Dbt5], vec![Dbt1])));
+}
+
 #[test]
 fn test_add() {
   assert_eq!(ternary("&1.1") + ternary("&9.9"), ternary("&5"));
@@ -61,6 +86,110 @@ fn test_add() {
   assert_eq!(ternary("&1.6") + ternary("&1.6"), ternary("&17.4"));
 }
 
+#[test]
+fn add_in_place() {
+  assert_eq!(ternary("&.1").add_at(-1, Dbt1), ternary("&1.9"));
+  assert_eq!(ternary("&11").add_at(0, Dbt1), ternary("&199"));
+  let mut v = ternary("&5");
+  v.add_at_mut(3, Dbt1);
+  v.add_at_mut(-2, Dbt9);
+  assert_eq!(v, ternary("&1555.59"));
+  assert_eq!(ternary("&1.1") + ternary("&11111.55551"), ternary("&199999.15551"));
+  assert_eq!(ternary("&8888") + ternary("&2222"), ternary("&5"));
+}
+
+#[test]
+fn reference_ops() {
+  let a = ternary("&1.1");
+  let b = ternary("&9.41");
+  assert_eq!(&a + &b, a.to_owned() + b.to_owned());
+  assert_eq!(&a + b.to_owned(), a.to_owned() + &b);
+  assert_eq!(&a - &b, a.to_owned() - b.to_owned());
+  assert_eq!(&a - b.to_owned(), a.to_owned() - &b);
+  assert_eq!(&b - &a, -(&a - &b));
+  assert_eq!(&a * &b, a.to_owned() * b.to_owned());
+  assert_eq!(&a * b.to_owned(), a.to_owned() * &b);
+  assert_eq!(&ternary("&111") / &ternary("&23"), ternary("&47"));
+  assert_eq!(ternary("&111") / &ternary("&23"), &ternary("&111") / ternary("&23"));
+  assert_eq!(-&a, ternary("&9.9"));
+}
+
+#[test]
+fn assign_ops() {
+  let mut v = ternary("&1.1");
+  v += ternary("&1.1");
+  assert_eq!(v, ternary("&15.9"));
+  v -= &ternary("&1.1");
+  assert_eq!(v, ternary("&1.1"));
+  v *= &ternary("&3");
+  assert_eq!(v, ternary("&3.3"));
+  v *= ternary("&1.3");
+  assert_eq!(v, ternary("&3.49"));
+  v /= &ternary("&3.3");
+  assert_eq!(v, ternary("&1.3"));
+  v /= ternary("&1.3");
+  assert_eq!(v, ternary("&1"));
+  v -= ternary("&1");
+  assert!(v.is_zero());
+}
+
+#[test]
+fn digit_ops() {
+  assert_eq!(ternary("&14") * Dbt3, ternary("&14") * ternary("&3"));
+  assert_eq!(&ternary("&66.66") * Dbt8, ternary("&66.66") * ternary("&8"));
+  assert_eq!(ternary("&743316.2") * Dbt2, ternary("&743316.2") * ternary("&2"));
+  assert_eq!(ternary("&14") * Dbt5, ternary("&5"));
+  assert_eq!(ternary("&11") + Dbt1, ternary("&199"));
+  assert_eq!(&ternary("&11") - Dbt1, ternary("&11") - ternary("&1"));
+  let mut v = ternary("&1.1");
+  v *= Dbt3;
+  v += Dbt7;
+  v -= Dbt3;
+  assert_eq!(v, ternary("&3.3") + ternary("&7") - ternary("&3"));
+}
+
+#[test]
+fn long_multiply() {
+  // (3^40 + 1) * (3^40 - 1), long enough for Karatsuba splitting
+  let a = ternary(&format!("&1{}1", "5".repeat(39)));
+  let b = ternary(&format!("&1{}9", "5".repeat(39)));
+  assert_eq!(a.to_owned() * b.to_owned(), ternary(&format!("&1{}9", "5".repeat(79))));
+  assert_eq!(a.to_owned() * b.to_owned(), b.to_owned() * a.to_owned());
+
+  let c = ternary(&format!("&{}.{}", "2468".repeat(20), "1397".repeat(10)));
+  assert_eq!((a.to_owned() + b.to_owned()) * c.to_owned(), a * c.to_owned() + b * c);
+}
+
+#[test]
+fn complex_arithmetic() {
+  let a = ComplexXy::new(1.0, 2.0);
+  let b = ComplexXy::from((-1.0, 1.0));
+  assert_eq!(a + b, ComplexXy::new(0.0, 3.0));
+  assert_eq!(a - b, ComplexXy::new(2.0, 1.0));
+  assert_eq!(-a, ComplexXy::new(-1.0, -2.0));
+  assert_eq!(a * b, ComplexXy::new(-1.0, 3.0));
+  assert_eq!((a * b) / b, a);
+  assert_eq!(a.conjugate(), ComplexXy::new(-1.0, 2.0));
+  assert_eq!(a.norm_sqr(), 5.0);
+  assert_eq!(ComplexXy::new(3.0, -4.0).norm(), 5.0);
+  assert_eq!(ComplexXy::new(3.0, -4.0).l1_norm(), 7.0);
+  assert_eq!(<(f64, f64)>::from(a), (1.0, 2.0));
+
+  // same as multiplying digits
+  for (p, q) in [("&3", "&3"), ("&14", "&66"), ("&19", "&46"), ("&743316", "&616")] {
+    assert_eq!(
+      ComplexXy::from(ternary(p)) * ComplexXy::from(ternary(q)),
+      ComplexXy::from(ternary(p) * ternary(q))
+    );
+    assert_eq!(ComplexXy::from(ternary(p)).conjugate(), ComplexXy::from(ternary(p).conjugate()));
+  }
+  assert_eq!(DualBalancedTernary::try_from(ComplexXy::new(1.0, 2.0)), Ok(ternary("&14")));
+  assert_eq!(
+    DualBalancedTernary::try_from(ComplexXy::new(f64::NAN, 0.0)),
+    Err(DbtError::NonFiniteFloat)
+  );
+}
+
 #[test]
 fn test_complex() {
   assert_eq!(ComplexXy::from(ternary("&4")), ComplexXy { x: 1.0, y: -1.0 });
@@ -136,10 +265,41 @@ fn test_divide() {
   // there was a bug in mutiply conjugated values
   assert_eq!(
     ternary("&9.41658555559") / ternary("&9.51372555559"),
-    ternary("&1.653732945268634852684471755515159")
+    ternary("&1.65374713977323481664837579119191")
   );
 }
 
+#[test]
+fn test_checked_divide() {
+  assert_eq!(ternary("&111").checked_div(&ternary("&23")), Some(ternary("&47")));
+  assert_eq!(ternary("&111").checked_div(&ternary("&5")), None);
+  assert_eq!(ternary("&5").checked_div(&ternary("&5")), None);
+  assert_eq!(ternary("&111").try_div(&ternary("&5")), Err(DbtError::DivisionByZero));
+  assert_eq!(ternary("&3.49").try_div(&ternary("&3.3")), Ok(ternary("&1.3")));
+
+  let q = ternary("&743316").try_quotient(&ternary("&616")).unwrap();
+  assert_eq!(q.value, ternary("&751"));
+  assert!(q.is_exact);
+
+  let q = ternary("&743317").try_quotient(&ternary("&616")).unwrap();
+  assert_eq!(q.value, ternary("&743317") / ternary("&616"));
+  assert!(!q.is_exact);
+
+  assert!(ternary("&5").try_quotient(&ternary("&616")).unwrap().is_exact);
+}
+
+#[test]
+#[should_panic(expected = "&5 is not a valid divisor")]
+fn divide_by_zero() {
+  let _ = ternary("&111") / ternary("&5");
+}
+
+#[test]
+#[should_panic(expected = "&5 is not a valid divisor")]
+fn linear_divide_by_zero() {
+  let _ = ternary("&111").linear_divide(ternary("&5"));
+}
+
 #[test]
 fn test_round() {
   assert_eq!(ternary("&2.4").round(), ternary("&2"));
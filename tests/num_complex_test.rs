@@ -0,0 +1,54 @@
+#![cfg(feature = "num-complex")]
+
+extern crate dual_balanced_ternary;
+
+use std::convert::TryFrom;
+
+use num_complex::Complex;
+
+use dual_balanced_ternary::complex::ComplexXy;
+use dual_balanced_ternary::{ternary, DbtError, DualBalancedTernary};
+
+#[test]
+fn float_complex() {
+  // `re` is on `1` direction, `im` on `3` direction
+  assert_eq!(Complex::<f64>::from(ternary("&14")), Complex::new(2.0, 1.0));
+  assert_eq!(Complex::<f64>::from(ternary("&.7")), Complex::new(0.0, -1.0 / 3.0));
+  assert_eq!(DualBalancedTernary::try_from(Complex::new(2.0, 1.0)), Ok(ternary("&14")));
+  assert_eq!(
+    DualBalancedTernary::try_from(Complex::new(0.0, f64::INFINITY)),
+    Err(DbtError::NonFiniteFloat)
+  );
+
+  assert_eq!(Complex::<f64>::from(ComplexXy::new(1.0, 2.0)), Complex::new(2.0, 1.0));
+  assert_eq!(ComplexXy::from(Complex::new(2.0, 1.0)), ComplexXy::new(1.0, 2.0));
+}
+
+#[test]
+fn integer_complex() {
+  assert_eq!(DualBalancedTernary::from(Complex::new(2i64, 1i64)), ternary("&14"));
+  assert_eq!(DualBalancedTernary::from(Complex::new(0i64, 1i64)), ternary("&3"));
+  assert_eq!(Complex::<i64>::try_from(ternary("&66")), Ok(Complex::new(4, -4)));
+  assert_eq!(Complex::<i64>::try_from(ternary("&1.1")), Err(DbtError::NonIntegralValue));
+
+  let big = Complex::new(i64::MIN, i64::MAX);
+  assert_eq!(Complex::<i64>::try_from(DualBalancedTernary::from(big)), Ok(big));
+  let over = DualBalancedTernary::from((0i128, i64::MAX as i128 + 1));
+  assert_eq!(Complex::<i64>::try_from(over), Err(DbtError::IntegerOverflow));
+}
+
+#[test]
+fn same_as_reference() {
+  let values = ["&1", "&3", "&14", "&66", "&743316", "&616", "&19", "&248"];
+  for p in values {
+    for q in values {
+      let (a, b) = (ternary(p), ternary(q));
+      let expected = Complex::<f64>::from(a.to_owned()) * Complex::<f64>::from(b.to_owned());
+      assert_eq!(Complex::<f64>::from(a.to_owned() * b.to_owned()), expected);
+      assert_eq!(
+        Complex::<f64>::from(a.to_owned() + b.to_owned()),
+        Complex::<f64>::from(a) + Complex::<f64>::from(b)
+      );
+    }
+  }
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "num-traits")]
+
+extern crate dual_balanced_ternary;
+
+use num_traits::{FromPrimitive, Inv, Num, One, Pow, ToPrimitive, Zero};
+
+use dual_balanced_ternary::{ternary, DbtError, DualBalancedTernary};
+
+/// generic code only knowing `Num`
+fn sum_of_squares<T: Num + Clone>(xs: &[T]) -> T {
+  xs.iter().fold(T::zero(), |acc, x| acc + x.clone() * x.clone())
+}
+
+#[test]
+fn generic_numeric() {
+  assert_eq!(DualBalancedTernary::zero(), ternary("&5"));
+  assert_eq!(DualBalancedTernary::one(), ternary("&1"));
+  assert!(Zero::is_zero(&ternary("&55.5")));
+  assert!(ternary("&1").is_one());
+
+  // 1 + i, 2 squared
+  assert_eq!(
+    sum_of_squares(&[ternary("&8"), ternary("&19")]),
+    ternary("&11") + ternary("&3") + ternary("&3")
+  );
+  assert_eq!(num_traits::pow(ternary("&3"), 2), ternary("&9"));
+}
+
+#[test]
+fn from_str_radix() {
+  assert_eq!(DualBalancedTernary::from_str_radix("&1.1", 3), Ok(ternary("&1.1")));
+  assert_eq!(DualBalancedTernary::from_str_radix("&19", 10), Ok(ternary("&19")));
+  assert_eq!(DualBalancedTernary::from_str_radix("7", 10), Ok(ternary("&191")));
+  assert_eq!(DualBalancedTernary::from_str_radix("-10", 3), Ok(ternary("&95")));
+  assert_eq!(
+    DualBalancedTernary::from_str_radix("ff", 16),
+    DualBalancedTernary::from_i64(255).ok_or(DbtError::EmptyNumber)
+  );
+  assert_eq!(
+    DualBalancedTernary::from_str_radix("-12", 2),
+    Err(DbtError::InvalidDigit { ch: '2', position: 2 })
+  );
+  assert_eq!(DualBalancedTernary::from_str_radix("-", 10), Err(DbtError::EmptyNumber));
+  assert_eq!(
+    DualBalancedTernary::from_str_radix("&1.0", 3),
+    Err(DbtError::InvalidDigit { ch: '0', position: 3 })
+  );
+}
+
+#[test]
+fn primitives() {
+  assert_eq!(DualBalancedTernary::from_i64(-4), Some(ternary("&99")));
+  assert_eq!(DualBalancedTernary::from_u128(u128::MAX).and_then(|v| v.to_u128()), Some(u128::MAX));
+  assert_eq!(DualBalancedTernary::from_f64(0.5), Some(ternary("&.1111111111")));
+  assert_eq!(DualBalancedTernary::from_f64(f64::NAN), None);
+
+  assert_eq!(ternary("&191").to_i64(), Some(7));
+  assert_eq!(ternary("&9").to_u32(), None);
+  assert_eq!(ternary("&1.1").to_i64(), None);
+  assert_eq!(ternary("&3").to_i64(), None);
+  assert_eq!(ternary("&1.1").to_f64(), Some(4.0 / 3.0));
+  assert_eq!(ternary("&3").to_f64(), None);
+}
+
+#[test]
+fn inv_and_pow() {
+  assert_eq!(ternary("&3").inv(), ternary("&7"));
+  // same digits as `/`, also for quotients that do not terminate
+  assert_eq!(ternary("&11").inv(), ternary("&.19191919191919191919"));
+  for s in ["&11", "&8", "&1.1", "&12", "&14", "&743316"] {
+    assert_eq!(ternary(s).inv(), ternary("&1") / ternary(s), "{}", s);
+  }
+  assert_eq!(ternary("&3").pow(4u32), ternary("&1"));
+  assert_eq!(ternary("&19").pow(10usize), DualBalancedTernary::from(1024));
+  assert_eq!(ternary("&8").pow(0u32), ternary("&1"));
+}
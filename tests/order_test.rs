@@ -0,0 +1,75 @@
+extern crate dual_balanced_ternary;
+
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+use dual_balanced_ternary::{ternary, DualBalancedTernary};
+
+#[test]
+fn total_order() {
+  for a in -20i64..20 {
+    for b in -20i64..20 {
+      assert_eq!(DualBalancedTernary::from(a).cmp(&DualBalancedTernary::from(b)), a.cmp(&b));
+    }
+  }
+  assert!(ternary("&.1") < ternary("&1"));
+  assert!(ternary("&.9") > ternary("&9"));
+  assert!(ternary("&1.9") > ternary("&.1111"));
+  assert!(ternary("&3") > ternary("&5"));
+  assert!(ternary("&7") < ternary("&5"));
+  // `1` direction comes first
+  assert!(ternary("&4") < ternary("&6"));
+  assert_eq!(ternary("&1.15").cmp(&ternary("&51.1")), Ordering::Equal);
+
+  let set: BTreeSet<DualBalancedTernary> = ["&1", "&9", "&5", "&3", "&7", "&19", "&.1"].iter().map(|s| ternary(s)).collect();
+  let sorted: Vec<String> = set.iter().map(|v| v.to_string()).collect();
+  assert_eq!(sorted, vec!["&9", "&7", "&5", "&3", "&.1", "&1", "&19"]);
+}
+
+#[test]
+fn axis_comparators() {
+  assert_eq!(ternary("&4").cmp_y(&ternary("&9")), Ordering::Equal);
+  assert_eq!(ternary("&4").cmp_x(&ternary("&9")), Ordering::Greater);
+  assert_eq!(ternary("&13").cmp_x(&ternary("&3")), Ordering::Equal);
+  assert_eq!(ternary("&13").cmp_y(&ternary("&3")), Ordering::Greater);
+  assert_eq!(ternary("&.3").cmp_x(&ternary("&7")), Ordering::Greater);
+}
+
+#[test]
+fn norm_comparator() {
+  assert_eq!(ternary("&3").cmp_norm(&ternary("&1")), Ordering::Equal);
+  assert_eq!(ternary("&8").cmp_norm(&ternary("&9")), Ordering::Greater);
+  assert_eq!(ternary("&.1").cmp_norm(&ternary("&7")), Ordering::Less);
+  assert_eq!(ternary("&5").cmp_norm(&ternary("&.5551")), Ordering::Less);
+
+  let mut values: Vec<DualBalancedTernary> = ["&19", "&8", "&5", "&.4", "&66"].iter().map(|s| ternary(s)).collect();
+  values.sort_by(DualBalancedTernary::cmp_norm);
+  let sorted: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+  assert_eq!(sorted, vec!["&5", "&.4", "&8", "&19", "&66"]);
+}
+
+#[test]
+fn angle_comparator() {
+  let mut points = vec![];
+  for x in -3i64..=3 {
+    for y in -3i64..=3 {
+      points.push((x, y));
+    }
+  }
+  for (ax, ay) in &points {
+    for (bx, by) in &points {
+      let a_angle = (*ax as f64).atan2(*ay as f64);
+      let b_angle = (*bx as f64).atan2(*by as f64);
+      let expected = if (a_angle - b_angle).abs() < 1e-12 {
+        Ordering::Equal
+      } else {
+        a_angle.partial_cmp(&b_angle).unwrap()
+      };
+      let a = DualBalancedTernary::from((*ax, *ay));
+      let b = DualBalancedTernary::from((*bx, *by));
+      assert_eq!(a.cmp_angle(&b), expected, "{} {}", a, b);
+    }
+  }
+  assert_eq!(ternary("&.1").cmp_angle(&ternary("&1")), Ordering::Equal);
+  assert_eq!(ternary("&.3").cmp_angle(&ternary("&1.3")), Ordering::Greater);
+}
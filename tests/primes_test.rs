@@ -0,0 +1,93 @@
+extern crate dual_balanced_ternary;
+
+use dual_balanced_ternary::{ternary, DbtError, DualBalancedTernary};
+
+#[test]
+fn test_is_prime() {
+  assert!(ternary("&8").is_prime());
+  assert!(ternary("&14").is_prime());
+  assert!(ternary("&15").is_prime());
+  assert!(ternary("&75").is_prime());
+  assert!(!ternary("&19").is_prime());
+  assert!(!ternary("&11").is_prime());
+  assert!(!ternary("&1").is_prime());
+  assert!(!ternary("&3").is_prime());
+  assert!(!ternary("&5").is_prime());
+  assert!(!ternary("&1.1").is_prime());
+}
+
+#[test]
+fn test_sieve() {
+  assert_eq!(
+    DualBalancedTernary::primes_within(10),
+    vec![ternary("&8"), ternary("&14"), ternary("&36"), ternary("&15")]
+  );
+  assert_eq!(DualBalancedTernary::primes_within(1), vec![]);
+
+  let primes = DualBalancedTernary::primes_within(200);
+  for p in &primes {
+    assert!(p.is_prime(), "{} is not prime", p);
+    assert_eq!(&p.normalize_associate(), p);
+  }
+  let mut count = 0;
+  for x in 0..15 {
+    for y in 1..15 {
+      if x * x + y * y <= 200 && DualBalancedTernary::new(x as f64, y as f64).is_prime() {
+        count += 1;
+      }
+    }
+  }
+  assert_eq!(primes.len(), count);
+}
+
+#[test]
+fn test_factorize() {
+  let f = ternary("&19").factorize().unwrap();
+  assert_eq!(f.unit, ternary("&7"));
+  assert_eq!(f.primes, vec![(ternary("&8"), 2)]);
+  assert_eq!(format!("{}", f), "&7 * &8^2");
+  assert_eq!(format!("{}", ternary("&15").factorize().unwrap()), "&15");
+  assert_eq!(format!("{}", ternary("&9").factorize().unwrap()), "&9");
+
+  assert_eq!(ternary("&5").factorize(), Err(DbtError::ZeroValue));
+  assert_eq!(ternary("&1.1").factorize(), Err(DbtError::NonIntegralValue));
+
+  // norm `3^80` still fits, `3^82` does not
+  let big = ternary(&format!("&1{}", "5".repeat(40)));
+  let f = big.factorize().unwrap();
+  assert_eq!(f.primes, vec![(ternary("&15"), 40)]);
+  let long = ternary(&format!("&1{}", "5".repeat(41)));
+  assert_eq!(long.factorize(), Err(DbtError::IntegerOverflow));
+
+  // `2^31 - 1` is decided by trial division, while its norm `2^62` is too large to factorize
+  let small = DualBalancedTernary::from((1i64 << 31) - 1);
+  assert_eq!(small.try_is_prime(), Ok(true));
+  assert_eq!(small.factorize(), Err(DbtError::TrialDivisionLimit));
+  // `2^61 - 1` is past the limit
+  let large = DualBalancedTernary::from((1i64 << 61) - 1);
+  assert_eq!(large.try_is_prime(), Err(DbtError::TrialDivisionLimit));
+  assert_eq!(large.factorize(), Err(DbtError::TrialDivisionLimit));
+  // a prime left over after trial division, the norm `2^40 + 1` is `257 * 4278255361`
+  let v = DualBalancedTernary::from((1i64, 1i64 << 20));
+  assert_eq!(v.factorize().map(|f| f.primes.len()), Ok(2));
+  // a norm close to `2^126` with small factors
+  assert!(DualBalancedTernary::from(i64::MAX).factorize().is_ok());
+
+  for x in -20..20 {
+    for y in -20..20 {
+      if x == 0 && y == 0 {
+        continue;
+      }
+      let v = DualBalancedTernary::new(x as f64, y as f64);
+      let f = v.factorize().unwrap();
+      let mut product = f.unit.to_owned();
+      for (p, count) in &f.primes {
+        assert!(p.is_prime());
+        for _ in 0..*count {
+          product *= p;
+        }
+      }
+      assert_eq!(product, v, "{} gives {}", v, f);
+    }
+  }
+}
@@ -0,0 +1,60 @@
+extern crate dual_balanced_ternary;
+
+use dual_balanced_ternary::{ternary, ArithmeticContext, DbtError, DbtRational, RepeatingTernary};
+
+fn rational(a: &str, b: &str) -> DbtRational {
+  DbtRational::new(ternary(a), ternary(b)).unwrap()
+}
+
+#[test]
+fn reduce() {
+  assert_eq!(rational("&19", "&11"), rational("&1", "&19"));
+  assert_eq!(rational("&111", "&23"), DbtRational::from(ternary("&47")));
+  assert_eq!(rational("&.1", "&1"), rational("&1", "&15"));
+  assert_eq!(rational("&5", "&3"), DbtRational::from(ternary("&5")));
+  assert_eq!(DbtRational::new(ternary("&1"), ternary("&5")), Err(DbtError::DivisionByZero));
+
+  // denominator is moved to the associate with positive `1` side
+  assert_eq!(rational("&3", "&9").numerator(), &ternary("&7"));
+  assert_eq!(rational("&1", "&3").denominator(), &ternary("&1"));
+  assert_eq!(rational("&743317", "&616").denominator(), &ternary("&838"));
+  assert_eq!(rational("&743317", "&616").numerator(), &ternary("&129931"));
+
+  assert_eq!(format!("{}", rational("&1", "&19")), "&1/&19");
+  assert_eq!(format!("{}", rational("&2", "&8")), "&9");
+  assert!(rational("&2", "&8").is_integral());
+  assert!(rational("&5", "&8").is_zero());
+}
+
+#[test]
+fn exact_arithmetic() {
+  let half = rational("&1", "&19");
+  let third = rational("&1", "&15");
+  assert_eq!(third.clone() + third.clone() + third.clone(), DbtRational::from(ternary("&1")));
+  assert_eq!(half.clone() - third.clone(), rational("&1", "&195"));
+  assert_eq!(half.clone() * half.clone(), rational("&1", "&11"));
+  assert_eq!(half.clone() / third.clone(), rational("&15", "&19"));
+  assert_eq!(-half.clone(), rational("&9", "&19"));
+  assert_eq!(half.recip(), Ok(DbtRational::from(ternary("&19"))));
+  assert_eq!(half.try_div(&rational("&5", "&1")), Err(DbtError::DivisionByZero));
+
+  // dividing and multiplying back does not drift
+  let mut x = DbtRational::from(ternary("&743317"));
+  let d = DbtRational::from(ternary("&616"));
+  for _ in 0..3 {
+    x = x / d.clone();
+  }
+  for _ in 0..3 {
+    x = x * d.clone();
+  }
+  assert_eq!(x, DbtRational::from(ternary("&743317")));
+}
+
+#[test]
+fn convert_back() {
+  let ctx = ArithmeticContext::with_precision(6);
+  assert_eq!(rational("&1", "&19").to_ternary(&ctx), ternary("&.111111"));
+  assert_eq!(rational("&1.1", "&14").to_ternary(&ctx), ternary("&1.242684"));
+  assert_eq!(rational("&1", "&19").to_repeating(), "&.(1)".parse::<RepeatingTernary>());
+  assert_eq!(rational("&1.1", "&14").to_repeating(), "&1.2(4268)".parse::<RepeatingTernary>());
+}
@@ -0,0 +1,81 @@
+extern crate dual_balanced_ternary;
+
+use dual_balanced_ternary::{ternary, DbtError, DualBalancedTernaryDigit::*, RepeatingTernary};
+
+fn repeating(s: &str) -> RepeatingTernary {
+  s.parse().unwrap()
+}
+
+#[test]
+fn parse_and_format() {
+  assert_eq!(format!("{}", repeating("&1.65(3732)")), "&1.65(3732)");
+  assert_eq!(format!("{}", repeating("&.(1)")), "&.(1)");
+  assert_eq!(format!("{}", repeating("&1.1")), "&1.1");
+  assert_eq!(format!("{}", repeating("&1.15")), "&1.1");
+
+  // shortest period, started as early as possible
+  assert_eq!(format!("{}", repeating("&.(11)")), "&.(1)");
+  assert_eq!(format!("{}", repeating("&.1(1)")), "&.(1)");
+  assert_eq!(format!("{}", repeating("&.2(42)")), "&.(24)");
+  assert_eq!(format!("{}", repeating("&1.5(5)")), "&1");
+  assert_eq!(format!("{}", repeating("&.5(1)")), "&.5(1)");
+
+  assert_eq!(repeating("&1.65(3732)").period(), &[Dbt3, Dbt7, Dbt3, Dbt2]);
+  assert_eq!(repeating("&1.65(3732)").non_repeating(), ternary("&1.6"));
+  assert!(repeating("&1.1").is_finite());
+  assert!(!repeating("&.(1)").is_finite());
+
+  assert_eq!("&1.(".parse::<RepeatingTernary>(), Err(DbtError::UnclosedPeriod));
+  assert_eq!("&1.()".parse::<RepeatingTernary>(), Err(DbtError::UnclosedPeriod));
+  assert_eq!(
+    "&1(1)".parse::<RepeatingTernary>(),
+    Err(DbtError::InvalidDigit { ch: '(', position: 2 })
+  );
+  assert_eq!(
+    "&1.(10)".parse::<RepeatingTernary>(),
+    Err(DbtError::InvalidDigit { ch: '0', position: 5 })
+  );
+
+  // positions count characters, not bytes
+  assert_eq!(
+    "&1.é(1)".parse::<RepeatingTernary>(),
+    Err(DbtError::InvalidDigit { ch: 'é', position: 3 })
+  );
+  assert_eq!(
+    "&→(1)".parse::<RepeatingTernary>(),
+    Err(DbtError::InvalidDigit { ch: '(', position: 2 })
+  );
+  assert_eq!(
+    "&1.1(1é)".parse::<RepeatingTernary>(),
+    Err(DbtError::InvalidDigit { ch: 'é', position: 6 })
+  );
+}
+
+#[test]
+fn expand() {
+  assert_eq!(repeating("&.(1)").to_ternary(4), ternary("&.1111"));
+  assert_eq!(repeating("&1.65(3732)").to_ternary(1), ternary("&1.6"));
+  assert_eq!(repeating("&1.65(3732)").to_ternary(8), ternary("&1.65373237"));
+  assert_eq!(RepeatingTernary::from(ternary("&12.34")).to_ternary(10), ternary("&12.34"));
+}
+
+#[test]
+fn divide_repeating() {
+  assert_eq!(ternary("&111").div_repeating(&ternary("&23")), Ok(repeating("&47")));
+  assert_eq!(ternary("&1").div_repeating(&ternary("&19")), Ok(repeating("&.(1)")));
+  assert_eq!(ternary("&1").div_repeating(&ternary("&11")), Ok(repeating("&.(19)")));
+  assert_eq!(ternary("&1").div_repeating(&ternary("&8")), Ok(repeating("&.(6)")));
+  assert_eq!(ternary("&1.1").div_repeating(&ternary("&14")), Ok(repeating("&1.2(4268)")));
+  assert_eq!(ternary("&4").div_repeating(&ternary("&1.1")), Ok(repeating("&4.(64)")));
+  assert_eq!(ternary("&1").div_repeating(&ternary("&5")), Err(DbtError::DivisionByZero));
+
+  let q = ternary("&743317").div_repeating(&ternary("&616")).unwrap();
+  assert_eq!(q.non_repeating(), ternary("&751"));
+  assert_eq!(q.period().len(), 268);
+  assert_eq!(q.to_ternary(20), ternary("&751.55295196541773481441"));
+
+  assert_eq!(
+    ternary("&9.41658555559").div_repeating_within(&ternary("&9.51372555559"), 100),
+    Err(DbtError::PeriodTooLong(100))
+  );
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "serde")]
+
+extern crate dual_balanced_ternary;
+
+use serde::{Deserialize, Serialize};
+use serde_test::{assert_tokens, Configure, Token};
+
+use dual_balanced_ternary::codec::{encode_nibbles, encode_packed};
+use dual_balanced_ternary::{ternary, DualBalancedTernary, DualBalancedTernaryDigit::*};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Point {
+  at: DualBalancedTernary,
+  #[serde(with = "dual_balanced_ternary::serde_as::xy")]
+  xy: DualBalancedTernary,
+  #[serde(with = "dual_balanced_ternary::serde_as::string")]
+  name: DualBalancedTernary,
+  #[serde(with = "dual_balanced_ternary::serde_as::bytes")]
+  buf: DualBalancedTernary,
+}
+
+#[test]
+fn readable_and_compact() {
+  let v = ternary("&12.3");
+  assert_tokens(&v.to_owned().readable(), &[Token::Str("&12.3")]);
+  let packed: &'static [u8] = Box::leak(encode_packed(&v).into_boxed_slice());
+  assert_tokens(&v.compact(), &[Token::Bytes(packed)]);
+
+  assert_tokens(&Dbt7, &[Token::U8(7)]);
+}
+
+#[test]
+fn json_forms() {
+  let p = Point {
+    at: ternary("&1.1"),
+    xy: ternary("&14"),
+    name: ternary("&9"),
+    buf: ternary("&1"),
+  };
+  let json = serde_json::to_string(&p).unwrap();
+  assert_eq!(
+    json,
+    r#"{"at":"&1.1","xy":{"x":1.0,"y":2.0},"name":"&9","buf":[219,254,1,0,80,115]}"#
+  );
+  assert_eq!(serde_json::from_str::<Point>(&json).unwrap(), p);
+
+  // nibble buffers are read as well
+  let nibbles = serde_json::to_string(&encode_nibbles(&ternary("&1"))).unwrap();
+  let json = format!(r#"{{"at":"&5","xy":{{"x":0.0,"y":0.5}},"name":"&.1","buf":{}}}"#, nibbles);
+  let p: Point = serde_json::from_str(&json).unwrap();
+  assert_eq!(p.at, ternary("&5"));
+  assert_eq!(p.xy, DualBalancedTernary::new(0.0, 0.5));
+  assert_eq!(p.buf, ternary("&1"));
+
+  assert!(serde_json::from_str::<DualBalancedTernary>(r#""1.1""#).is_err());
+  assert!(serde_json::from_str::<Point>(r#"{"at":"&0"}"#).is_err());
+  assert_eq!(serde_json::to_string(&vec![Dbt1, Dbt9]).unwrap(), "[1,9]");
+  assert!(serde_json::from_str::<Vec<dual_balanced_ternary::DualBalancedTernaryDigit>>("[0]").is_err());
+}
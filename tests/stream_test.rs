@@ -0,0 +1,128 @@
+extern crate dual_balanced_ternary;
+
+use std::convert::TryFrom;
+use std::io;
+
+use dual_balanced_ternary::codec::{decode_prefix, encode_nibbles};
+use dual_balanced_ternary::{ternary, DbtError, DbtReader, DbtWriter, DualBalancedTernary};
+
+#[test]
+fn write_and_read() -> io::Result<()> {
+  let values = vec![
+    ternary("&1.1"),
+    ternary("&5"),
+    ternary("&3445647.674"),
+    ternary(&format!("&{}", "19".repeat(200))),
+  ];
+  let mut writer = DbtWriter::new(vec![]);
+  for v in &values {
+    writer.write(v)?;
+  }
+  let buf = writer.into_inner();
+
+  // reading from a borrowed slice
+  let mut reader = DbtReader::new(&buf[..]);
+  for v in &values {
+    assert_eq!(reader.read()?.as_ref(), Some(v));
+  }
+  assert_eq!(reader.read()?, None);
+
+  let read: Vec<DualBalancedTernary> = DbtReader::new(&buf[..]).collect::<io::Result<_>>()?;
+  assert_eq!(read, values);
+
+  // nibble buffers can be put in the same stream
+  let mut mixed = encode_nibbles(&ternary("&12.3"));
+  mixed.extend_from_slice(&buf);
+  assert_eq!(DbtReader::new(&mixed[..]).count(), 5);
+
+  Ok(())
+}
+
+#[test]
+fn decode_slices() {
+  let mut buf = encode_nibbles(&ternary("&12.3"));
+  buf.extend_from_slice(&encode_nibbles(&ternary("&1")));
+  let (first, used) = decode_prefix(&buf).unwrap();
+  assert_eq!(first, ternary("&12.3"));
+  assert_eq!(decode_prefix(&buf[used..]), Ok((ternary("&1"), 5)));
+  assert_eq!(DualBalancedTernary::try_from(&buf[used..]), Ok(ternary("&1")));
+  assert_eq!(decode_prefix(&[1, 21, 21]), Err(DbtError::UnknownFormat));
+}
+
+#[test]
+fn stream_errors() {
+  let mut writer = DbtWriter::new(vec![]);
+  writer.write(&ternary("&3445647.674")).unwrap();
+  let buf = writer.into_inner();
+
+  let e = DbtReader::new(&buf[..buf.len() - 1]).read().unwrap_err();
+  assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+  let e = DbtReader::new(&buf[..3]).read().unwrap_err();
+  assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+
+  let e = DbtReader::new(&[1u8, 21, 21][..]).read().unwrap_err();
+  assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+  assert_eq!(e.to_string(), DbtError::UnknownFormat.to_string());
+
+  let e = DbtReader::new(&[0xDBu8, 0x01, 1, 0, 0xa5][..]).read().unwrap_err();
+  assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+
+  // a huge length is not allocated ahead
+  let e = DbtReader::new(&[0xDBu8, 0xFE, 0xff, 0xff, 0xff, 0xff, 0x0f, 0, 0][..])
+    .read()
+    .unwrap_err();
+  assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn iterate_until_error() {
+  let mut writer = DbtWriter::new(vec![]);
+  writer.write(&ternary("&1.1")).unwrap();
+  let mut buf = writer.into_inner();
+  // a bad frame, followed by bytes that would read as another value when resynchronized by accident
+  buf.extend_from_slice(&[1, 21, 21]);
+  buf.extend_from_slice(&encode_nibbles(&ternary("&12.3")));
+
+  let mut reader = DbtReader::new(&buf[..]);
+  assert_eq!(reader.next().unwrap().unwrap(), ternary("&1.1"));
+  assert_eq!(reader.next().unwrap().unwrap_err().kind(), io::ErrorKind::InvalidData);
+  assert!(reader.next().is_none());
+  assert!(reader.next().is_none());
+
+  let mut reader = DbtReader::new(&buf[..0]);
+  assert!(reader.next().is_none());
+  assert!(reader.next().is_none());
+}
+
+/// fails every other read with `Interrupted`, like a reader hit by signals
+struct Interrupting<'a> {
+  inner: &'a [u8],
+  interrupt: bool,
+}
+
+impl io::Read for Interrupting<'_> {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    self.interrupt = !self.interrupt;
+    if self.interrupt {
+      return Err(io::ErrorKind::Interrupted.into());
+    }
+    self.inner.read(buf)
+  }
+}
+
+#[test]
+fn read_interrupted() -> io::Result<()> {
+  let mut writer = DbtWriter::new(vec![]);
+  writer.write(&ternary("&1.1"))?;
+  writer.write(&ternary("&3445647.674"))?;
+  let buf = writer.into_inner();
+
+  let mut reader = DbtReader::new(Interrupting {
+    inner: &buf,
+    interrupt: false,
+  });
+  assert_eq!(reader.read()?, Some(ternary("&1.1")));
+  assert_eq!(reader.read()?, Some(ternary("&3445647.674")));
+  assert_eq!(reader.read()?, None);
+  Ok(())
+}